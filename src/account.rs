@@ -0,0 +1,270 @@
+use bip39::Mnemonic;
+use bitcoin::Network;
+use bitcoin::address::AddressType;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpub};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+use crate::address::{AddressInfo, FreeWebMovementAddress, MnemonicInfo};
+use crate::consts::{BIP44_PURPOSE, BIP86_PURPOSE, COIN_TYPE};
+use crate::error::AccountError;
+
+/// BIP44 中的 change 层：0 为收款链，1 为找零链
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    Receive = 0,
+    Change = 1,
+}
+
+/// 需要持久化到钱包文件中的账户索引
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountIndexes {
+    pub account: u32,
+    pub next_receive_index: u32,
+    pub next_change_index: u32,
+}
+
+/// HD 账户：保存助记词，按需派生 m/purpose'/coin_type'/account'/change/index
+#[derive(Clone)]
+pub struct HdAccount {
+    pub mnemonic: Mnemonic,
+    passphrase: String,
+    purpose: u32,
+    coin_type: u32,
+    pub network: Network,
+    pub address_type: AddressType,
+    pub prefix: String,
+    pub indexes: AccountIndexes,
+}
+
+impl Drop for HdAccount {
+    fn drop(&mut self) {
+        self.passphrase.zeroize();
+    }
+}
+
+impl HdAccount {
+    pub fn new(
+        mnemonic: Mnemonic,
+        passphrase: &str,
+        account: u32,
+        network: Network,
+        address_type: AddressType,
+    ) -> Self {
+        HdAccount {
+            mnemonic,
+            passphrase: passphrase.to_string(),
+            purpose: default_purpose(address_type),
+            coin_type: COIN_TYPE,
+            network,
+            address_type,
+            prefix: AddressInfo::new(network, address_type).prefix,
+            indexes: AccountIndexes {
+                account,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 由已有地址构造账户，并校验 passphrase 能派生出该地址；
+    /// purpose、coin_type 和 account 取自地址的派生路径，后续地址与它位于同一账户
+    pub fn from_address(
+        address: &FreeWebMovementAddress,
+        passphrase: &str,
+        indexes: AccountIndexes,
+    ) -> Result<Self, AccountError> {
+        let (purpose, coin_type, account) = parse_account_path(&address.info.derivation_path)?;
+        let account = HdAccount {
+            mnemonic: address.mnemonic.clone(),
+            passphrase: passphrase.to_string(),
+            purpose,
+            coin_type,
+            network: address.info.network,
+            address_type: address.info.address_type,
+            prefix: address.info.prefix.clone(),
+            indexes: AccountIndexes { account, ..indexes },
+        };
+        let check = account.derive_path(&address.info.derivation_path)?;
+        if check.public_key != address.public_key {
+            return Err(AccountError::PassphraseMismatch);
+        }
+        Ok(account)
+    }
+
+    pub fn purpose(&self) -> u32 {
+        self.purpose
+    }
+
+    /// 账户层路径 m/purpose'/coin_type'/account'
+    pub fn account_path(&self) -> String {
+        format!(
+            "m/{}'/{}'/{}'",
+            self.purpose, self.coin_type, self.indexes.account
+        )
+    }
+
+    /// 账户层扩展公钥，可用于构造只读钱包
+    pub fn xpub(&self) -> Result<Xpub, AccountError> {
+        let seed = FreeWebMovementAddress::mnemonic_to_seed(&self.mnemonic, &self.passphrase);
+        FreeWebMovementAddress::to_xpub(&seed, &self.account_path(), self.network)
+    }
+
+    pub fn derivation_path(&self, chain: Chain, index: u32) -> String {
+        format!(
+            "m/{}'/{}'/{}'/{}/{}",
+            self.purpose, self.coin_type, self.indexes.account, chain as u32, index
+        )
+    }
+
+    pub fn derive(&self, chain: Chain, index: u32) -> Result<FreeWebMovementAddress, AccountError> {
+        self.derive_path(&self.derivation_path(chain, index))
+    }
+
+    fn derive_path(&self, path: &str) -> Result<FreeWebMovementAddress, AccountError> {
+        let mnemonic_info = MnemonicInfo {
+            language: self.mnemonic.language(),
+            word_count: self.mnemonic.word_count(),
+            phrase: self.mnemonic.to_string().into(),
+            passphrase: self.passphrase.clone().into(),
+        };
+        let address_info = AddressInfo {
+            derivation_path: path.to_string(),
+            network: self.network,
+            address_type: self.address_type,
+            prefix: self.prefix.clone(),
+        };
+        FreeWebMovementAddress::try_new(mnemonic_info, Some(address_info))
+    }
+
+    /// 派生下一个未使用的收款地址，并递增索引
+    pub fn next_receive_address(&mut self) -> Result<FreeWebMovementAddress, AccountError> {
+        let address = self.derive(Chain::Receive, self.indexes.next_receive_index)?;
+        self.indexes.next_receive_index += 1;
+        Ok(address)
+    }
+
+    /// 派生下一个未使用的找零地址，并递增索引
+    pub fn next_change_address(&mut self) -> Result<FreeWebMovementAddress, AccountError> {
+        let address = self.derive(Chain::Change, self.indexes.next_change_index)?;
+        self.indexes.next_change_index += 1;
+        Ok(address)
+    }
+}
+
+fn default_purpose(address_type: AddressType) -> u32 {
+    match address_type {
+        AddressType::P2tr => BIP86_PURPOSE,
+        _ => BIP44_PURPOSE,
+    }
+}
+
+/// 解析 m/purpose'/coin_type'/account'/change/index，返回 (purpose, coin_type, account)；
+/// 不符合该布局的路径无法确定后续地址所在的分支，直接拒绝
+fn parse_account_path(path: &str) -> Result<(u32, u32, u32), AccountError> {
+    match DerivationPath::from_str(path)?.as_ref() {
+        [
+            ChildNumber::Hardened { index: purpose },
+            ChildNumber::Hardened { index: coin_type },
+            ChildNumber::Hardened { index: account },
+            ChildNumber::Normal { index: 0 | 1 },
+            ChildNumber::Normal { .. },
+        ] => Ok((*purpose, *coin_type, *account)),
+        _ => Err(AccountError::WalletAccount(format!(
+            "derivation path {} is not m/purpose'/coin_type'/account'/change/index",
+            path
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::DERIVATION_PATH;
+    use bip39::Language;
+
+    #[test]
+    fn test_hd_account_derivation() {
+        let address = FreeWebMovementAddress::random();
+        let mut account =
+            HdAccount::from_address(&address, "", AccountIndexes::default()).expect("账户构造失败");
+
+        assert_eq!(account.derivation_path(Chain::Receive, 0), DERIVATION_PATH);
+        assert_eq!(
+            account.derivation_path(Chain::Change, 3),
+            "m/44'/1010086'/0'/1/3"
+        );
+
+        // 第 0 个收款地址即默认地址
+        let first = account.next_receive_address().unwrap();
+        let second = account.next_receive_address().unwrap();
+        let change = account.next_change_address().unwrap();
+        assert_eq!(first.to_string(), address.to_string());
+        assert_ne!(first.to_string(), second.to_string());
+        assert_ne!(second.to_string(), change.to_string());
+        assert_eq!(second.info.derivation_path, "m/44'/1010086'/0'/0/1");
+        assert_eq!(account.indexes.next_receive_index, 2);
+        assert_eq!(account.indexes.next_change_index, 1);
+
+        // 错误的 passphrase 被拒绝
+        assert!(HdAccount::from_address(&address, "wrong", AccountIndexes::default()).is_err());
+    }
+
+    #[test]
+    fn test_hd_account_taproot_purpose() {
+        let mnemonic = Mnemonic::generate_in(Language::English, 12).unwrap();
+        let account = HdAccount::new(mnemonic, "", 2, Network::Testnet, AddressType::P2tr);
+        assert_eq!(
+            account.derivation_path(Chain::Receive, 5),
+            "m/86'/1010086'/2'/0/5"
+        );
+        let derived = account.derive(Chain::Receive, 5).unwrap();
+        assert!(derived.address.to_string().starts_with("tb1p"));
+    }
+
+    #[test]
+    fn test_hd_account_follows_address_path() {
+        let mnemonic_info = || MnemonicInfo {
+            language: Language::English,
+            word_count: 12,
+            phrase: crate::test_utils::TEST_PHRASE.to_string().into(),
+            passphrase: String::new().into(),
+        };
+        let address_at = |path: &str, address_type| {
+            let address_info = AddressInfo {
+                derivation_path: path.to_string(),
+                ..AddressInfo::new(Network::Bitcoin, address_type)
+            };
+            FreeWebMovementAddress::new(mnemonic_info(), Some(address_info))
+        };
+
+        // BIP44 路径上的 P2tr 地址，后续地址仍在同一分支
+        let address = address_at("m/44'/1010086'/3'/0/0", AddressType::P2tr);
+        let mut account = HdAccount::from_address(&address, "", AccountIndexes::default()).unwrap();
+        assert_eq!(account.indexes.account, 3);
+        assert_eq!(account.account_path(), "m/44'/1010086'/3'");
+        let next = account.next_receive_address().unwrap();
+        assert_eq!(next.to_string(), address.to_string());
+        assert_eq!(
+            account.next_change_address().unwrap().info.derivation_path,
+            "m/44'/1010086'/3'/1/0"
+        );
+
+        // 其他币种的 coin_type 同样沿用
+        let address = address_at("m/84'/0'/0'/0/7", AddressType::P2wpkh);
+        let account = HdAccount::from_address(&address, "", AccountIndexes::default()).unwrap();
+        assert_eq!(
+            account.derivation_path(Chain::Receive, 7),
+            address.info.derivation_path
+        );
+
+        // 非标准布局无法确定下一个地址，直接拒绝
+        for path in ["m/0'/0'", "m/44'/1010086'/0'/2/0", "m/44'/1010086'/0/0/0"] {
+            let address = address_at(path, AddressType::P2pkh);
+            assert!(matches!(
+                HdAccount::from_address(&address, "", AccountIndexes::default()),
+                Err(AccountError::WalletAccount(_))
+            ));
+        }
+    }
+}
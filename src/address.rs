@@ -1,28 +1,45 @@
 use bip39::{Language, Mnemonic};
-use bitcoin::address::AddressType;
-use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::address::{AddressType, NetworkUnchecked};
+use bitcoin::bip32::{ChainCode, DerivationPath, Xpriv, Xpub};
 use bitcoin::{Address, Network, PrivateKey, PublicKey};
 use hmac::Hmac;
 use pbkdf2::pbkdf2;
-use secp256k1::{Message, Secp256k1, ecdsa::Signature};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use secp256k1::{Keypair, Message, Secp256k1, XOnlyPublicKey, ecdsa::Signature, schnorr};
 use sha2::{Digest, Sha256, Sha512};
-use std::io::{self, Read, Write};
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::Path;
 use std::str::FromStr;
 use std::{fmt, fs};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 use crate::consts::{
     COIN_PREFIX, DERIVATION_PATH, MNEMONIC_SEED_ROUNDS, MNEMONIC_SEED_SIZE, MNEMONIC_STR,
-    MNEMONIC_WORD_COUNT,
+    MNEMONIC_WORD_COUNT, SCHNORR_MESSAGE_TAG, TAPROOT_DERIVATION_PATH,
 };
+use crate::error::AccountError;
+use crate::mnemonic::{detect_language, language_name, parse_language};
+use crate::storage::atomic_write;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 #[repr(C)]
 pub struct MnemonicInfo {
     pub language: Language,
     pub word_count: usize,
-    pub phrase: String,
-    pub passphrase: String,
+    pub phrase: Zeroizing<String>,
+    pub passphrase: Zeroizing<String>,
+}
+
+/// 随钱包保存的助记词元数据，不含助记词和 passphrase 本身
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MnemonicMeta {
+    #[serde(with = "crate::address::serde_language")]
+    pub language: Language,
+    pub word_count: usize,
+    pub has_passphrase: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,23 +53,175 @@ pub struct AddressInfo {
     pub prefix: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 由助记词派生的地址；0.2.0 起含私有字段，须通过 new / try_new 等构造函数创建
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedAddressRecord")]
 #[repr(C)]
 pub struct FreeWebMovementAddress {
     pub info: AddressInfo,
-    #[serde(with = "crate::address::serde_mnemonic")]
+    #[serde(serialize_with = "crate::address::serde_mnemonic::serialize")]
     pub mnemonic: Mnemonic,
-    #[serde(with = "crate::address::serde_address")]
+    /// 由助记词和 passphrase 决定，不可单独修改，通过 mnemonic_meta() 读取
+    mnemonic_meta: MnemonicMeta,
+    #[serde(serialize_with = "crate::address::serde_address::serialize")]
     pub address: Address,
     #[serde(with = "crate::address::serde_pubkey")]
     pub public_key: PublicKey,
+    #[serde(serialize_with = "crate::address::serde_privkey::serialize")]
+    pub private_key: ErasingPrivateKey,
+}
+
+const REDACTED: &str = "<redacted>";
+
+impl fmt::Debug for MnemonicInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MnemonicInfo")
+            .field("language", &self.language)
+            .field("word_count", &self.word_count)
+            .field("phrase", &REDACTED)
+            .field("passphrase", &REDACTED)
+            .finish()
+    }
+}
+
+impl MnemonicInfo {
+    /// 由已有助记词构造，自动识别语言和单词数
+    pub fn from_phrase(phrase: &str, passphrase: &str) -> Result<Self, AccountError> {
+        let language = detect_language(phrase)?;
+        Ok(MnemonicInfo {
+            language,
+            word_count: phrase.split_whitespace().count(),
+            phrase: phrase.to_string().into(),
+            passphrase: passphrase.to_string().into(),
+        })
+    }
+}
+
+impl fmt::Debug for FreeWebMovementAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FreeWebMovementAddress")
+            .field("info", &self.info)
+            .field("mnemonic", &REDACTED)
+            .field("mnemonic_meta", &self.mnemonic_meta)
+            .field("address", &self.address)
+            .field("public_key", &self.public_key)
+            .field("private_key", &REDACTED)
+            .finish()
+    }
+}
+
+/// 地址中的私钥，离开作用域时擦除；通过 Deref 按 PrivateKey 使用。
+/// 助记词由 bip39 的 zeroize 特性负责擦除
+#[derive(Clone, PartialEq, Eq)]
+pub struct ErasingPrivateKey(PrivateKey);
+
+impl Deref for ErasingPrivateKey {
+    type Target = PrivateKey;
+
+    fn deref(&self) -> &PrivateKey {
+        &self.0
+    }
+}
+
+impl From<PrivateKey> for ErasingPrivateKey {
+    fn from(private_key: PrivateKey) -> Self {
+        ErasingPrivateKey(private_key)
+    }
+}
+
+impl fmt::Debug for ErasingPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl Drop for ErasingPrivateKey {
+    fn drop(&mut self) {
+        self.0.inner.non_secure_erase();
+    }
+}
+
+impl ZeroizeOnDrop for ErasingPrivateKey {}
+
+/// 派生过程中的扩展私钥，离开作用域时擦除私钥和链码
+pub(crate) struct ErasingXpriv(pub(crate) Xpriv);
+
+impl Drop for ErasingXpriv {
+    fn drop(&mut self) {
+        self.0.private_key.non_secure_erase();
+        self.0.chain_code = ChainCode::from([0u8; 32]);
+    }
+}
+
+/// 反序列化的中间结构：地址的网络需要与同一记录中的 info.network 校验，
+/// 助记词按保存的语言解析，旧文件没有元数据时自动识别语言
+#[derive(Deserialize)]
+struct UncheckedAddressRecord {
+    info: AddressInfo,
+    mnemonic: String,
+    #[serde(default)]
+    mnemonic_meta: Option<MnemonicMeta>,
+    #[serde(deserialize_with = "crate::address::serde_address::deserialize")]
+    address: Address<NetworkUnchecked>,
+    #[serde(with = "crate::address::serde_pubkey")]
+    public_key: PublicKey,
     #[serde(with = "crate::address::serde_privkey")]
-    pub private_key: PrivateKey,
+    private_key: PrivateKey,
+}
+
+impl TryFrom<UncheckedAddressRecord> for FreeWebMovementAddress {
+    type Error = AccountError;
+
+    fn try_from(record: UncheckedAddressRecord) -> Result<Self, Self::Error> {
+        let mut phrase = record.mnemonic;
+        let language = match record.mnemonic_meta {
+            Some(meta) => meta.language,
+            None => detect_language(&phrase)?,
+        };
+        let mnemonic = Mnemonic::parse_in(language, phrase.as_str());
+        phrase.zeroize();
+        let mnemonic = mnemonic?;
+        let mnemonic_meta = MnemonicMeta {
+            language,
+            word_count: mnemonic.word_count(),
+            // 旧文件无法得知是否使用了 passphrase，按未使用处理
+            has_passphrase: record.mnemonic_meta.is_some_and(|meta| meta.has_passphrase),
+        };
+
+        let address = record.address.require_network(record.info.network)?;
+        Ok(FreeWebMovementAddress {
+            info: record.info,
+            mnemonic,
+            mnemonic_meta,
+            address,
+            public_key: record.public_key,
+            private_key: record.private_key.into(),
+        })
+    }
+}
+
+impl AddressInfo {
+    /// 按地址类型选择默认派生路径（P2tr 使用 BIP86，其余使用 BIP44）
+    pub fn new(network: Network, address_type: AddressType) -> Self {
+        AddressInfo {
+            derivation_path: AddressInfo::default_derivation_path(address_type).to_string(),
+            network,
+            address_type,
+            prefix: COIN_PREFIX.to_string(),
+        }
+    }
+
+    pub fn default_derivation_path(address_type: AddressType) -> &'static str {
+        match address_type {
+            AddressType::P2tr => TAPROOT_DERIVATION_PATH,
+            _ => DERIVATION_PATH,
+        }
+    }
 }
 
 pub mod serde_network {
     use super::*;
-    use serde::{Deserializer, Serializer, de};
+    use serde::{Deserializer, Serializer, de, ser};
 
     pub fn serialize<S>(network: &Network, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -63,7 +232,12 @@ pub mod serde_network {
             Network::Testnet => "Testnet",
             Network::Signet => "Signet",
             Network::Regtest => "Regtest",
-            _ => "",
+            other => {
+                return Err(ser::Error::custom(format!(
+                    "Unsupported Network: {}",
+                    other
+                )));
+            }
         };
 
         serializer.serialize_str(network_str)
@@ -87,10 +261,33 @@ pub mod serde_network {
     }
 }
 
-pub mod serde_address_type {
+/// 可选的 Network，旧文件中没有该字段时为 None
+pub mod serde_option_network {
     use super::*;
     use serde::{Deserializer, Serializer};
 
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "super::serde_network")] Network);
+
+    pub fn serialize<S>(network: &Option<Network>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        network.map(Wrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Network>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|w| w.0))
+    }
+}
+
+pub mod serde_address_type {
+    use super::*;
+    use serde::{Deserializer, Serializer, de, ser};
+
     pub fn serialize<S>(address_type: &AddressType, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -101,7 +298,12 @@ pub mod serde_address_type {
             AddressType::P2wpkh => "P2wpkh",
             AddressType::P2wsh => "P2wsh",
             AddressType::P2tr => "P2tr",
-            _ => "",
+            other => {
+                return Err(ser::Error::custom(format!(
+                    "Unsupported address type: {}",
+                    other
+                )));
+            }
         };
         serializer.serialize_str(address_type_str)
     }
@@ -118,7 +320,12 @@ pub mod serde_address_type {
             "P2wpkh" => AddressType::P2wpkh,
             "P2wsh" => AddressType::P2wsh,
             "P2tr" => AddressType::P2tr,
-            _ => panic!("Unknown address type: {}", address_type_str), // 或者用默认值
+            other => {
+                return Err(de::Error::custom(format!(
+                    "Unknown address type: {}",
+                    other
+                )));
+            }
         };
         Ok(s)
     }
@@ -155,11 +362,32 @@ pub mod serde_mnemonic {
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<Mnemonic, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = Zeroizing::new(String::deserialize(deserializer)?);
+        let language = detect_language(&s).map_err(serde::de::Error::custom)?;
+        Mnemonic::parse_in(language, s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+pub mod serde_language {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(language: &Language, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(language_name(*language))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Language, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        Mnemonic::parse(&s).map_err(serde::de::Error::custom)
+        parse_language(&s).map_err(serde::de::Error::custom)
     }
 }
 
@@ -174,15 +402,13 @@ pub mod serde_address {
         serializer.serialize_str(&address.to_string())
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Address, D::Error>
+    /// 只解析地址格式，网络由调用方根据 info.network 校验
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Address<NetworkUnchecked>, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        let unchecked = Address::from_str(&s).map_err(serde::de::Error::custom)?;
-        unchecked
-            .require_network(Network::Bitcoin)
-            .map_err(serde::de::Error::custom)
+        Address::from_str(&s).map_err(serde::de::Error::custom)
     }
 }
 
@@ -226,64 +452,97 @@ pub mod serde_privkey {
     }
 }
 
+pub mod serde_xpub {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(xpub: &Xpub, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&xpub.to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Xpub, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Xpub::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[allow(dead_code)]
 impl FreeWebMovementAddress {
     pub fn new(mnemonic_info: MnemonicInfo, address_info_option: Option<AddressInfo>) -> Self {
-        let mnemonic: Mnemonic;
-        if mnemonic_info.phrase.is_empty() {
-            mnemonic = Mnemonic::generate_in(mnemonic_info.language, mnemonic_info.word_count)
-                .expect("hardcoded mnemonic params");
+        FreeWebMovementAddress::try_new(mnemonic_info, address_info_option)
+            .expect("invalid mnemonic params, use try_new or mnemonic::validate_mnemonic")
+    }
+
+    pub fn try_new(
+        mnemonic_info: MnemonicInfo,
+        address_info_option: Option<AddressInfo>,
+    ) -> Result<Self, AccountError> {
+        let mnemonic = if mnemonic_info.phrase.is_empty() {
+            Mnemonic::generate_in(mnemonic_info.language, mnemonic_info.word_count)?
         } else {
-            mnemonic = Mnemonic::parse_in(mnemonic_info.language, mnemonic_info.phrase.clone())
-                .expect("hardcoded mnemonic params");
-        }
+            Mnemonic::parse_in(mnemonic_info.language, mnemonic_info.phrase.as_str())?
+        };
 
         // 默认地址信息
 
-        let address_info = address_info_option.unwrap_or(AddressInfo {
-            derivation_path: DERIVATION_PATH.to_string(),
-            network: Network::Bitcoin,
-            address_type: AddressType::P2pkh,
-            prefix: COIN_PREFIX.to_string(),
-        });
+        let address_info = address_info_option
+            .unwrap_or_else(|| AddressInfo::new(Network::Bitcoin, AddressType::P2pkh));
 
-        let seed: [u8; MNEMONIC_SEED_SIZE] = FreeWebMovementAddress::mnemonic_to_seed(
-            &mnemonic.clone(),
-            &mnemonic_info.passphrase.clone(),
-        );
-        let (public_key, private_key) = FreeWebMovementAddress::to_key_pair(
-            seed,
+        let seed = FreeWebMovementAddress::mnemonic_to_seed(&mnemonic, &mnemonic_info.passphrase);
+        let (public_key, private_key) = FreeWebMovementAddress::try_to_key_pair(
+            &seed,
             &address_info.derivation_path,
             address_info.network,
-        )
-        .expect("hardcoded key operations");
-        let address = FreeWebMovementAddress::key_to_inner_address(
+        )?;
+        let address = FreeWebMovementAddress::try_key_to_inner_address(
             public_key,
             address_info.network,
             address_info.address_type,
-        )
-        .expect("hardcoded address params");
+        )?;
+
+        let mnemonic_meta = MnemonicMeta {
+            language: mnemonic.language(),
+            word_count: mnemonic.word_count(),
+            has_passphrase: !mnemonic_info.passphrase.is_empty(),
+        };
 
-        FreeWebMovementAddress {
+        Ok(FreeWebMovementAddress {
             info: address_info,
             mnemonic,
+            mnemonic_meta,
             address,
             public_key,
-            private_key,
-        }
+            private_key: private_key.into(),
+        })
+    }
+
+    /// 助记词元数据：语言、单词数以及是否使用了 passphrase
+    pub fn mnemonic_meta(&self) -> MnemonicMeta {
+        self.mnemonic_meta
     }
 
     // Basic functions
 
-    pub fn mnemonic_to_seed(mnemonic: &Mnemonic, passphrase: &str) -> [u8; MNEMONIC_SEED_SIZE] {
-        let mut seed = [0u8; MNEMONIC_SEED_SIZE];
-        let salt = format!("{}{}", MNEMONIC_STR, passphrase);
-        let _ = pbkdf2::<Hmac<Sha512>>(
-            mnemonic.to_string().as_bytes(),
+    pub fn mnemonic_to_seed(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+    ) -> Zeroizing<[u8; MNEMONIC_SEED_SIZE]> {
+        let mut seed = Zeroizing::new([0u8; MNEMONIC_SEED_SIZE]);
+        let phrase = Zeroizing::new(mnemonic.to_string());
+        let salt = Zeroizing::new(format!("{}{}", MNEMONIC_STR, passphrase));
+        pbkdf2::<Hmac<Sha512>>(
+            phrase.as_bytes(),
             salt.as_bytes(),
             MNEMONIC_SEED_ROUNDS,
-            &mut seed,
-        );
+            seed.as_mut(),
+        )
+        .expect("HMAC accepts keys of any length");
         seed
     }
 
@@ -292,16 +551,27 @@ impl FreeWebMovementAddress {
         network: Network,
         address_type: AddressType, // 新增参数
     ) -> Result<Address, String> {
+        FreeWebMovementAddress::try_key_to_inner_address(key, network, address_type)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn try_key_to_inner_address(
+        key: PublicKey,
+        network: Network,
+        address_type: AddressType,
+    ) -> Result<Address, AccountError> {
         let address = match address_type {
             AddressType::P2pkh => Address::p2pkh(&key, network),
-            AddressType::P2wpkh => {
-                Address::p2wpkh(&key, network).expect("hardcoded address params")
-            }
-            AddressType::P2sh => {
-                Address::p2shwpkh(&key, network).expect("hardcoded address params")
+            AddressType::P2wpkh => Address::p2wpkh(&key, network)?,
+            AddressType::P2sh => Address::p2shwpkh(&key, network)?,
+            // BIP86：仅 key-path 花费，无脚本树，对 x-only 公钥做 tap tweak
+            AddressType::P2tr => {
+                let secp = Secp256k1::verification_only();
+                let (internal_key, _parity) = key.inner.x_only_public_key();
+                Address::p2tr(&secp, internal_key, None, network)
             }
-            _ => {
-                return Err("Unsupported address type".to_string());
+            other => {
+                return Err(AccountError::UnsupportedAddressType(other.to_string()));
             }
         };
         Ok(address)
@@ -312,73 +582,193 @@ impl FreeWebMovementAddress {
         dp: &str,
         network: Network,
     ) -> Result<(PublicKey, PrivateKey), String> {
+        let seed = Zeroizing::new(seed);
+        FreeWebMovementAddress::try_to_key_pair(&seed, dp, network).map_err(|e| e.to_string())
+    }
+
+    pub fn try_to_key_pair(
+        seed: &[u8; MNEMONIC_SEED_SIZE],
+        dp: &str,
+        network: Network,
+    ) -> Result<(PublicKey, PrivateKey), AccountError> {
+        let secp = Secp256k1::new();
+        let xprv = ErasingXpriv(Xpriv::new_master(network, seed)?);
+        let path = DerivationPath::from_str(dp)?;
+        let child_prv = ErasingXpriv(xprv.0.derive_priv(&secp, &path)?);
+        let child_pub = child_prv.0.to_priv().public_key(&secp);
+        Ok((child_pub, child_prv.0.to_priv()))
+    }
+
+    /// 导出指定路径（通常为账户层 m/44'/1010086'/account'）的扩展公钥
+    pub fn to_xpub(
+        seed: &[u8; MNEMONIC_SEED_SIZE],
+        dp: &str,
+        network: Network,
+    ) -> Result<Xpub, AccountError> {
         let secp = Secp256k1::new();
-        let xprv = Xpriv::new_master(network, &seed).map_err(|e| e.to_string())?;
-        let path = DerivationPath::from_str(dp).map_err(|e| e.to_string())?;
-        let child_prv = xprv.derive_priv(&secp, &path).map_err(|e| e.to_string())?;
-        let child_pub = child_prv.to_priv().public_key(&secp);
-        Ok((child_pub, child_prv.to_priv()))
+        let xprv = ErasingXpriv(Xpriv::new_master(network, seed)?);
+        let path = DerivationPath::from_str(dp)?;
+        let child_prv = ErasingXpriv(xprv.0.derive_priv(&secp, &path)?);
+        Ok(Xpub::from_priv(&secp, &child_prv.0))
     }
 
     pub fn sign_message(private_key: &PrivateKey, msg: &[u8]) -> Signature {
         let secp = Secp256k1::new();
-        let secret = private_key.inner;
         let hash = Sha256::digest(msg);
-        let message = Message::from_digest_slice(&hash).expect("valid hash");
-        secp.sign_ecdsa(&message, &secret)
+        let message = Message::from_digest(hash.into());
+        secp.sign_ecdsa(&message, &private_key.inner)
     }
 
     pub fn verify_message(public_key: &PublicKey, msg: &[u8], signature: &Signature) -> bool {
         let secp = Secp256k1::new();
         let hash = Sha256::digest(msg);
-        let message = Message::from_digest_slice(&hash).expect("valid hash");
+        let message = Message::from_digest(hash.into());
         secp.verify_ecdsa(&message, signature, &public_key.inner)
             .is_ok()
     }
 
-    pub fn to_public_key(bytes: &Vec<u8>) -> PublicKey {
-        PublicKey::from_slice(&bytes).expect("valid public key bytes")
+    /// BIP340 tagged hash: SHA256(SHA256(tag) || SHA256(tag) || msg)
+    pub fn tagged_hash(tag: &str, msg: &[u8]) -> [u8; 32] {
+        let tag_hash = Sha256::digest(tag.as_bytes());
+        let mut hasher = Sha256::new();
+        hasher.update(tag_hash);
+        hasher.update(tag_hash);
+        hasher.update(msg);
+        hasher.finalize().into()
+    }
+
+    pub fn to_x_only_public_key(public_key: &PublicKey) -> XOnlyPublicKey {
+        public_key.inner.x_only_public_key().0
+    }
+
+    /// BIP340 Schnorr 签名：消息先做 tagged hash；aux_rand 为 None 时使用系统随机数
+    pub fn sign_schnorr(
+        private_key: &PrivateKey,
+        msg: &[u8],
+        aux_rand: Option<&[u8; 32]>,
+    ) -> schnorr::Signature {
+        let digest = FreeWebMovementAddress::tagged_hash(SCHNORR_MESSAGE_TAG, msg);
+        FreeWebMovementAddress::sign_schnorr_digest(private_key, digest, aux_rand)
+    }
+
+    /// 直接对 32 字节摘要签名（BIP340 原始接口）
+    pub fn sign_schnorr_digest(
+        private_key: &PrivateKey,
+        digest: [u8; 32],
+        aux_rand: Option<&[u8; 32]>,
+    ) -> schnorr::Signature {
+        let secp = Secp256k1::signing_only();
+        let keypair = Keypair::from_secret_key(&secp, &private_key.inner);
+        let message = Message::from_digest(digest);
+        let aux = match aux_rand {
+            Some(aux) => *aux,
+            None => {
+                let mut aux = [0u8; 32];
+                OsRng.fill_bytes(&mut aux);
+                aux
+            }
+        };
+        secp.sign_schnorr_with_aux_rand(&message, &keypair, &aux)
+    }
+
+    pub fn verify_schnorr(
+        public_key: &XOnlyPublicKey,
+        msg: &[u8],
+        signature: &schnorr::Signature,
+    ) -> bool {
+        let digest = FreeWebMovementAddress::tagged_hash(SCHNORR_MESSAGE_TAG, msg);
+        FreeWebMovementAddress::verify_schnorr_digest(public_key, digest, signature)
+    }
+
+    pub fn verify_schnorr_digest(
+        public_key: &XOnlyPublicKey,
+        digest: [u8; 32],
+        signature: &schnorr::Signature,
+    ) -> bool {
+        let secp = Secp256k1::verification_only();
+        let message = Message::from_digest(digest);
+        secp.verify_schnorr(signature, &message, public_key).is_ok()
+    }
+
+    pub fn to_public_key(bytes: &[u8]) -> PublicKey {
+        FreeWebMovementAddress::try_to_public_key(bytes).expect("valid public key bytes")
+    }
+
+    pub fn try_to_public_key(bytes: &[u8]) -> Result<PublicKey, AccountError> {
+        Ok(PublicKey::from_slice(bytes)?)
+    }
+
+    pub fn to_private_key(bytes: &[u8], network: Network) -> PrivateKey {
+        FreeWebMovementAddress::try_to_private_key(bytes, network).expect("valid private key bytes")
+    }
+
+    pub fn try_to_private_key(bytes: &[u8], network: Network) -> Result<PrivateKey, AccountError> {
+        Ok(PrivateKey::from_slice(bytes, network)?)
+    }
+
+    pub fn to_signature(bytes: &[u8]) -> Signature {
+        FreeWebMovementAddress::try_to_signature(bytes).expect("valid signature bytes")
+    }
+
+    pub fn try_to_signature(bytes: &[u8]) -> Result<Signature, AccountError> {
+        Ok(Signature::from_compact(bytes)?)
+    }
+
+    pub fn to_schnorr_signature(bytes: &[u8]) -> schnorr::Signature {
+        FreeWebMovementAddress::try_to_schnorr_signature(bytes).expect("valid signature bytes")
     }
 
-    pub fn to_private_key(bytes: &Vec<u8>) -> PrivateKey {
-        PrivateKey::from_slice(&bytes, Network::Bitcoin).expect("valid private key bytes")
+    pub fn try_to_schnorr_signature(bytes: &[u8]) -> Result<schnorr::Signature, AccountError> {
+        Ok(schnorr::Signature::from_slice(bytes)?)
     }
 
-    pub fn to_signature(bytes: &Vec<u8>) -> Signature {
-        Signature::from_compact(&bytes).expect("valid signature bytes")
+    pub fn try_to_x_only_public_key(bytes: &[u8]) -> Result<XOnlyPublicKey, AccountError> {
+        XOnlyPublicKey::from_slice(bytes)
+            .map_err(|e| AccountError::Key(bitcoin::key::Error::Secp256k1(e)))
     }
 
     pub fn random() -> Self {
+        FreeWebMovementAddress::try_random().expect("hardcoded mnemonic params")
+    }
+
+    pub fn try_random() -> Result<Self, AccountError> {
         let mnemonic_info = MnemonicInfo {
             language: Language::English,
             word_count: MNEMONIC_WORD_COUNT,
-            phrase: String::new(),
-            passphrase: String::new(),
+            phrase: String::new().into(),
+            passphrase: String::new().into(),
         };
 
-        FreeWebMovementAddress::new(mnemonic_info, None)
+        FreeWebMovementAddress::try_new(mnemonic_info, None)
     }
 
     pub fn save_to_file(&self, path: &str) -> io::Result<()> {
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-        let mut file = fs::File::create(path)?;
-        file.write_all(json.as_bytes())?;
-        Ok(())
+        Ok(self.try_save_to_file(path)?)
+    }
+
+    pub fn try_save_to_file(&self, path: &str) -> Result<(), AccountError> {
+        let json = Zeroizing::new(serde_json::to_string_pretty(self)?);
+        atomic_write(Path::new(path), json.as_bytes())
     }
 
     pub fn from_json(json: &str) -> io::Result<Self> {
-        let addr =
-            serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-        Ok(addr)
+        Ok(FreeWebMovementAddress::try_from_json(json)?)
+    }
+
+    pub fn try_from_json(json: &str) -> Result<Self, AccountError> {
+        Ok(serde_json::from_str(json)?)
     }
 
     /// 从文件读取地址
     pub fn load_from_file(path: &str) -> io::Result<Self> {
+        Ok(FreeWebMovementAddress::try_load_from_file(path)?)
+    }
+
+    pub fn try_load_from_file(path: &str) -> Result<Self, AccountError> {
         let mut file = fs::File::open(path)?;
         let mut json = String::new();
         file.read_to_string(&mut json)?;
-        FreeWebMovementAddress::from_json(&json)
+        FreeWebMovementAddress::try_from_json(&json)
     }
 }
 
@@ -394,6 +784,7 @@ mod tests {
     use crate::address::FreeWebMovementAddress;
     use crate::address::MnemonicInfo;
     use crate::consts::*;
+    use crate::error::AccountError;
     use bip39::Language;
     use bip39::Mnemonic;
     use bitcoin::AddressType;
@@ -404,8 +795,8 @@ mod tests {
         let mi_en: MnemonicInfo = MnemonicInfo {
             language: Language::English,
             word_count: MNEMONIC_WORD_COUNT,
-            phrase: String::new(),
-            passphrase: String::new(),
+            phrase: String::new().into(),
+            passphrase: String::new().into(),
         };
 
         let mut mi_scn: MnemonicInfo = mi_en.clone();
@@ -415,21 +806,15 @@ mod tests {
 
         println!(
             "生成助记词: {}",
-            Mnemonic::generate_in(mi_en.language, mi_en.word_count)
-                .unwrap()
-                .to_string()
+            Mnemonic::generate_in(mi_en.language, mi_en.word_count).unwrap()
         );
         println!(
             "生成助记词: {}",
-            Mnemonic::generate_in(mi_scn.language, mi_en.word_count)
-                .unwrap()
-                .to_string()
+            Mnemonic::generate_in(mi_scn.language, mi_en.word_count).unwrap()
         );
         println!(
             "生成助记词: {}",
-            Mnemonic::generate_in(mi_tcn.language, mi_en.word_count)
-                .unwrap()
-                .to_string()
+            Mnemonic::generate_in(mi_tcn.language, mi_en.word_count).unwrap()
         );
 
         let ai = AddressInfo {
@@ -453,8 +838,9 @@ mod tests {
             word_count: MNEMONIC_WORD_COUNT,
             phrase: String::from(
                 "legal winner thank year wave sausage worth useful legal winner thank yellow",
-            ),
-            passphrase: String::new(),
+            )
+            .into(),
+            passphrase: String::new().into(),
         };
 
         let mi_en_phrase1: MnemonicInfo = MnemonicInfo {
@@ -462,8 +848,9 @@ mod tests {
             word_count: MNEMONIC_WORD_COUNT,
             phrase: String::from(
                 "legal winner thank year wave sausage worth useful legal winner thank yellow",
-            ),
-            passphrase: String::new(),
+            )
+            .into(),
+            passphrase: String::new().into(),
         };
 
         let mi_en_phrase2: MnemonicInfo = MnemonicInfo {
@@ -471,8 +858,9 @@ mod tests {
             word_count: MNEMONIC_WORD_COUNT,
             phrase: String::from(
                 "public refuse price sadness winter nose finger bomb damage corn expect marble",
-            ),
-            passphrase: String::new(),
+            )
+            .into(),
+            passphrase: String::new().into(),
         };
 
         let fwmaddress = FreeWebMovementAddress::new(mi_en.clone(), None);
@@ -481,17 +869,17 @@ mod tests {
         let fwmaddress3 = FreeWebMovementAddress::new(mi_en_phrase, Some(ai2.clone()));
         let fwmaddress4 = FreeWebMovementAddress::new(mi_en_phrase1, Some(ai2.clone()));
         let fwmaddress5 = FreeWebMovementAddress::new(mi_en_phrase2, Some(ai2.clone()));
-        // let fwmaddress6 = FreeWebMovementAddress::new(mi_en.clone(), Some(ai3.clone()));
-        // assert!(fwmaddress6.is_err());
+        let fwmaddress6 = FreeWebMovementAddress::new(mi_en.clone(), Some(ai3.clone()));
+        assert!(fwmaddress6.address.to_string().starts_with("bc1p"));
 
         assert_eq!(fwmaddress3.to_string(), fwmaddress4.to_string());
         assert_ne!(fwmaddress5.to_string(), fwmaddress4.to_string());
 
-        println!("生成地址: {}", fwmaddress.to_string());
-        println!("生成地址: {}", fwmaddress1.to_string());
-        println!("生成地址: {}", fwmaddress2.to_string());
-        println!("生成地址: {}", fwmaddress3.to_string());
-        println!("生成地址: {}", FreeWebMovementAddress::random().to_string());
+        println!("生成地址: {}", fwmaddress);
+        println!("生成地址: {}", fwmaddress1);
+        println!("生成地址: {}", fwmaddress2);
+        println!("生成地址: {}", fwmaddress3);
+        println!("生成地址: {}", FreeWebMovementAddress::random());
 
         let message = "Hello, FWM!".as_bytes();
         let signature = FreeWebMovementAddress::sign_message(&fwmaddress.private_key, message);
@@ -528,12 +916,12 @@ mod tests {
         assert_eq!(fwmaddress.public_key.to_string(), public_key.to_string());
 
         let bytes = fwmaddress.private_key.to_bytes();
-        let private_key = FreeWebMovementAddress::to_private_key(&bytes);
+        let private_key = FreeWebMovementAddress::to_private_key(&bytes, fwmaddress.info.network);
         assert_eq!(fwmaddress.private_key.to_string(), private_key.to_string());
 
         let signature = FreeWebMovementAddress::sign_message(&private_key, &[0, 1, 2]);
         let bytes = signature.serialize_compact();
-        let signature1 = FreeWebMovementAddress::to_signature(&bytes.to_vec());
+        let signature1 = FreeWebMovementAddress::to_signature(&bytes);
         assert_eq!(signature.to_string(), signature1.to_string());
     }
 
@@ -552,6 +940,243 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
+    #[test]
+    fn test_taproot_bip86() {
+        // BIP86 测试向量：m/86'/0'/0'/0/0
+        let mi = MnemonicInfo {
+            language: Language::English,
+            word_count: 12,
+            phrase: String::from("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").into(),
+            passphrase: String::new().into(),
+        };
+        let mut ai = AddressInfo::new(Network::Bitcoin, AddressType::P2tr);
+        assert_eq!(ai.derivation_path, TAPROOT_DERIVATION_PATH);
+        ai.derivation_path = String::from("m/86'/0'/0'/0/0");
+
+        let fwmaddress = FreeWebMovementAddress::new(mi, Some(ai));
+        assert_eq!(
+            fwmaddress.address.to_string(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        assert_eq!(fwmaddress.address.address_type(), Some(AddressType::P2tr));
+
+        let json = serde_json::to_string(&fwmaddress).unwrap();
+        let loaded = FreeWebMovementAddress::from_json(&json).unwrap();
+        assert_eq!(loaded.info.address_type, AddressType::P2tr);
+        assert_eq!(loaded.to_string(), fwmaddress.to_string());
+    }
+
+    #[test]
+    fn test_non_mainnet_serde() {
+        for network in [Network::Testnet, Network::Signet, Network::Regtest] {
+            for address_type in [AddressType::P2pkh, AddressType::P2wpkh, AddressType::P2tr] {
+                let mi = MnemonicInfo {
+                    language: Language::English,
+                    word_count: 12,
+                    phrase: String::new().into(),
+                    passphrase: String::new().into(),
+                };
+                let ai = AddressInfo::new(network, address_type);
+                let fwmaddress = FreeWebMovementAddress::new(mi, Some(ai));
+
+                let json = serde_json::to_string(&fwmaddress).unwrap();
+                let loaded = FreeWebMovementAddress::from_json(&json).expect("反序列化失败");
+                assert_eq!(loaded.info.network, network);
+                assert_eq!(loaded.to_string(), fwmaddress.to_string());
+                assert_eq!(loaded.private_key.to_wif(), fwmaddress.private_key.to_wif());
+
+                let bytes = fwmaddress.private_key.to_bytes();
+                let private_key = FreeWebMovementAddress::to_private_key(&bytes, network);
+                assert_eq!(private_key.to_wif(), fwmaddress.private_key.to_wif());
+            }
+        }
+
+        // 地址与 info.network 不一致时拒绝加载
+        let regtest = FreeWebMovementAddress::new(
+            MnemonicInfo {
+                language: Language::English,
+                word_count: 12,
+                phrase: String::new().into(),
+                passphrase: String::new().into(),
+            },
+            Some(AddressInfo::new(Network::Regtest, AddressType::P2wpkh)),
+        );
+        let mut value = serde_json::to_value(&regtest).unwrap();
+        value["info"]["network"] = serde_json::Value::from("Bitcoin");
+        assert!(FreeWebMovementAddress::from_json(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_schnorr_sign_verify() {
+        // BIP340 测试向量 0
+        let secret =
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000003")
+                .unwrap();
+        let private_key = FreeWebMovementAddress::to_private_key(&secret, Network::Bitcoin);
+        let public_key = FreeWebMovementAddress::to_x_only_public_key(
+            &private_key.public_key(&secp256k1::Secp256k1::new()),
+        );
+        assert_eq!(
+            hex::encode(public_key.serialize()),
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"
+        );
+        let signature =
+            FreeWebMovementAddress::sign_schnorr_digest(&private_key, [0; 32], Some(&[0; 32]));
+        assert_eq!(
+            hex::encode(signature.serialize()).to_uppercase(),
+            "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0"
+        );
+        assert!(FreeWebMovementAddress::verify_schnorr_digest(
+            &public_key,
+            [0; 32],
+            &signature
+        ));
+
+        // 钱包密钥 + tagged hash 消息
+        let fwmaddress = FreeWebMovementAddress::random();
+        let x_only = FreeWebMovementAddress::to_x_only_public_key(&fwmaddress.public_key);
+        let message = "Hello, FWM!".as_bytes();
+        let signature =
+            FreeWebMovementAddress::sign_schnorr(&fwmaddress.private_key, message, None);
+        assert!(FreeWebMovementAddress::verify_schnorr(
+            &x_only, message, &signature
+        ));
+        assert!(!FreeWebMovementAddress::verify_schnorr(
+            &x_only, b"other", &signature
+        ));
+
+        // 字节与十六进制往返
+        let bytes = signature.serialize();
+        let signature1 = FreeWebMovementAddress::to_schnorr_signature(&bytes);
+        assert_eq!(signature, signature1);
+        let signature2: secp256k1::schnorr::Signature = signature.to_string().parse().unwrap();
+        assert_eq!(signature, signature2);
+        let x_only1 =
+            FreeWebMovementAddress::try_to_x_only_public_key(&x_only.serialize()).unwrap();
+        assert_eq!(x_only, x_only1);
+        assert!(FreeWebMovementAddress::try_to_schnorr_signature(&bytes[..63]).is_err());
+    }
+
+    #[test]
+    fn test_try_constructors_return_errors() {
+        let bad_phrase = MnemonicInfo {
+            language: Language::English,
+            word_count: MNEMONIC_WORD_COUNT,
+            phrase: String::from("legal winner thank year wave sausage").into(),
+            passphrase: String::new().into(),
+        };
+        assert!(matches!(
+            FreeWebMovementAddress::try_new(bad_phrase, None),
+            Err(AccountError::Mnemonic(_))
+        ));
+
+        let bad_path = MnemonicInfo {
+            language: Language::English,
+            word_count: 12,
+            phrase: String::new().into(),
+            passphrase: String::new().into(),
+        };
+        let ai = AddressInfo {
+            derivation_path: String::from("m/44'/x"),
+            network: Network::Bitcoin,
+            address_type: AddressType::P2pkh,
+            prefix: COIN_PREFIX.to_string(),
+        };
+        assert!(matches!(
+            FreeWebMovementAddress::try_new(bad_path, Some(ai)),
+            Err(AccountError::Derivation(_))
+        ));
+
+        assert!(FreeWebMovementAddress::try_to_public_key(&[1, 2, 3]).is_err());
+        assert!(FreeWebMovementAddress::try_to_private_key(&[0; 31], Network::Bitcoin).is_err());
+        assert!(FreeWebMovementAddress::try_to_signature(&[0; 10]).is_err());
+
+        // 未知地址类型不再 panic
+        let mut value = serde_json::to_value(FreeWebMovementAddress::random()).unwrap();
+        value["info"]["address_type"] = serde_json::Value::from("P2xx");
+        assert!(FreeWebMovementAddress::from_json(&value.to_string()).is_err());
+        assert!(FreeWebMovementAddress::from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_meta_round_trip() {
+        for language in [
+            Language::Japanese,
+            Language::SimplifiedChinese,
+            Language::TraditionalChinese,
+        ] {
+            let phrase = Mnemonic::generate_in(language, 18).unwrap().to_string();
+            let mi = MnemonicInfo::from_phrase(&phrase, "secret").expect("识别语言失败");
+            assert_eq!(mi.word_count, 18);
+            let fwmaddress = FreeWebMovementAddress::try_new(mi, None).unwrap();
+            assert_eq!(fwmaddress.mnemonic.to_string(), phrase);
+            assert!(fwmaddress.mnemonic_meta.has_passphrase);
+
+            let json = serde_json::to_string(&fwmaddress).unwrap();
+            let loaded = FreeWebMovementAddress::from_json(&json).unwrap();
+            assert_eq!(loaded.mnemonic_meta, fwmaddress.mnemonic_meta);
+            assert_eq!(loaded.mnemonic.language(), fwmaddress.mnemonic.language());
+            assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+        }
+
+        // 没有元数据的旧文件自动识别语言
+        let fwmaddress = FreeWebMovementAddress::random();
+        let mut value = serde_json::to_value(&fwmaddress).unwrap();
+        assert_eq!(value["mnemonic_meta"]["language"], "english");
+        value.as_object_mut().unwrap().remove("mnemonic_meta");
+        let loaded = FreeWebMovementAddress::from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.mnemonic_meta, fwmaddress.mnemonic_meta);
+        assert!(!loaded.mnemonic_meta.has_passphrase);
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let fwmaddress = FreeWebMovementAddress::random();
+        let debug = format!("{:?}", fwmaddress);
+        assert!(debug.contains(&fwmaddress.address.to_string()));
+        assert!(!debug.contains(&fwmaddress.mnemonic.to_string()));
+        assert!(!debug.contains(&fwmaddress.private_key.to_wif()));
+        assert!(!debug.contains(&hex::encode(fwmaddress.private_key.to_bytes())));
+
+        let mi = MnemonicInfo {
+            language: Language::English,
+            word_count: 12,
+            phrase: fwmaddress.mnemonic.to_string().into(),
+            passphrase: String::from("secret passphrase").into(),
+        };
+        let debug = format!("{:?}", mi);
+        assert!(!debug.contains(mi.phrase.as_str()));
+        assert!(!debug.contains(mi.passphrase.as_str()));
+    }
+
+    #[test]
+    fn test_secret_fields_can_be_moved_out() {
+        // 擦除由字段类型负责，结构体本身没有 Drop，可以解构和使用结构体更新语法
+        let mi = MnemonicInfo {
+            language: Language::English,
+            word_count: 12,
+            phrase: String::new().into(),
+            passphrase: String::from("secret passphrase").into(),
+        };
+        let other = MnemonicInfo {
+            passphrase: String::new().into(),
+            ..mi
+        };
+        let MnemonicInfo { phrase, .. } = other;
+        assert!(phrase.is_empty());
+
+        let fwmaddress = FreeWebMovementAddress::random();
+        let wif = fwmaddress.private_key.to_wif();
+        let FreeWebMovementAddress {
+            address,
+            private_key,
+            ..
+        } = fwmaddress;
+        assert_eq!(private_key.to_wif(), wif);
+        assert_eq!(*private_key, bitcoin::PrivateKey::from_wif(&wif).unwrap());
+        assert!(!address.to_string().is_empty());
+    }
+
     #[test]
     fn test_basics() {
         println!("MAX_HUMAN_POPULATION: {}", MAX_HUMAN_POPULATION);
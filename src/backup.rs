@@ -0,0 +1,231 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, IsoWeek, NaiveDate, NaiveDateTime, Timelike};
+
+use crate::consts::BACKUP_TIMESTAMP_FORMAT;
+use crate::error::AccountError;
+
+/// 目录中一个带时间戳的备份文件：`<前缀><时间戳>[-序号].json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub created_at: NaiveDateTime,
+    /// 同一秒内的第几个备份，0 表示文件名不带序号
+    pub sequence: u32,
+}
+
+/// 备份保留策略：保留最近 keep_last 个，以及最近 keep_daily 天、keep_weekly 周中每天/每周最新的一个；
+/// 无论如何设置，最新的备份总会保留
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupRetention {
+    pub keep_last: usize,
+    pub keep_daily: usize,
+    pub keep_weekly: usize,
+}
+
+impl BackupRetention {
+    /// 返回按策略应删除的备份，backups 须按时间从旧到新排列
+    pub fn expired<'a>(&self, backups: &'a [BackupFile]) -> Vec<&'a BackupFile> {
+        let mut keep = vec![false; backups.len()];
+        let mut days: Vec<NaiveDate> = Vec::new();
+        let mut weeks: Vec<IsoWeek> = Vec::new();
+        for (rank, (i, backup)) in backups.iter().enumerate().rev().enumerate() {
+            if rank < self.keep_last.max(1) {
+                keep[i] = true;
+            }
+            let day = backup.created_at.date();
+            if !days.contains(&day) && days.len() < self.keep_daily {
+                days.push(day);
+                keep[i] = true;
+            }
+            let week = backup.created_at.iso_week();
+            if !weeks.contains(&week) && weeks.len() < self.keep_weekly {
+                weeks.push(week);
+                keep[i] = true;
+            }
+        }
+        backups
+            .iter()
+            .zip(keep)
+            .filter(|(_, keep)| !keep)
+            .map(|(backup, _)| backup)
+            .collect()
+    }
+}
+
+fn parse_backup_name(name: &str, prefix: &str) -> Option<(NaiveDateTime, u32)> {
+    let stem = name.strip_prefix(prefix)?.strip_suffix(".json")?;
+    let (timestamp, sequence) = match stem.split_once('-') {
+        Some((timestamp, sequence)) => (timestamp, sequence.parse().ok()?),
+        None => (stem, 0),
+    };
+    let created_at = NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()?;
+    Some((created_at, sequence))
+}
+
+/// 列出目录中以 prefix 开头的备份文件，按时间从旧到新排列；文件名不符合格式的文件被忽略
+pub fn list_backups(dir: &Path, prefix: &str) -> Result<Vec<BackupFile>, AccountError> {
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        if let Some((created_at, sequence)) =
+            parse_backup_name(&entry.file_name().to_string_lossy(), prefix)
+        {
+            backups.push(BackupFile {
+                path: entry.path(),
+                created_at,
+                sequence,
+            });
+        }
+    }
+    backups.sort_by_key(|b| (b.created_at, b.sequence));
+    Ok(backups)
+}
+
+/// 新备份的文件名；同一秒内已有备份时追加比现有序号更大的序号，避免覆盖，
+/// 且清理掉较早的文件后新备份仍排在最后
+pub fn backup_file_path(
+    dir: &Path,
+    prefix: &str,
+    now: NaiveDateTime,
+) -> Result<PathBuf, AccountError> {
+    let timestamp = now.format(BACKUP_TIMESTAMP_FORMAT);
+    // 文件名精确到秒
+    let created_at = now.with_nanosecond(0).unwrap_or(now);
+    let existing = if dir.exists() {
+        list_backups(dir, prefix)?
+    } else {
+        Vec::new()
+    };
+    let name = match existing
+        .iter()
+        .filter(|b| b.created_at == created_at)
+        .map(|b| b.sequence)
+        .max()
+    {
+        Some(sequence) => format!("{}{}-{}.json", prefix, timestamp, sequence + 1),
+        None => format!("{}{}.json", prefix, timestamp),
+    };
+    Ok(dir.join(name))
+}
+
+/// 按保留策略删除目录中以 prefix 开头的旧备份，返回被删除的路径
+pub fn prune_backups(
+    dir: &Path,
+    prefix: &str,
+    retention: &BackupRetention,
+) -> Result<Vec<PathBuf>, AccountError> {
+    let backups = list_backups(dir, prefix)?;
+    let mut removed = Vec::new();
+    for backup in retention.expired(&backups) {
+        std::fs::remove_file(&backup.path)?;
+        removed.push(backup.path.clone());
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn backup_at(timestamp: &str) -> BackupFile {
+        BackupFile {
+            path: PathBuf::from(format!("wallet_backup_{}.json", timestamp)),
+            created_at: NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).unwrap(),
+            sequence: 0,
+        }
+    }
+
+    #[test]
+    fn test_backup_retention() {
+        // 2026-03-02 为周一：两周内每天两个备份
+        let backups: Vec<BackupFile> = (2..16)
+            .flat_map(|day| {
+                [
+                    format!("202603{:02}080000", day),
+                    format!("202603{:02}200000", day),
+                ]
+            })
+            .map(|ts| backup_at(&ts))
+            .collect();
+        let names = |expired: Vec<&BackupFile>| -> Vec<String> {
+            let mut kept: Vec<String> = backups
+                .iter()
+                .filter(|b| !expired.contains(b))
+                .map(|b| b.created_at.format(BACKUP_TIMESTAMP_FORMAT).to_string())
+                .collect();
+            kept.reverse();
+            kept
+        };
+
+        let retention = BackupRetention {
+            keep_last: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(retention.expired(&backups)),
+            ["20260315200000", "20260315080000", "20260314200000"]
+        );
+
+        let retention = BackupRetention {
+            keep_last: 1,
+            keep_daily: 2,
+            keep_weekly: 2,
+        };
+        assert_eq!(
+            names(retention.expired(&backups)),
+            ["20260315200000", "20260314200000", "20260308200000"]
+        );
+
+        // 全为 0 时仍保留最新的备份
+        assert_eq!(
+            names(BackupRetention::default().expired(&backups)),
+            ["20260315200000"]
+        );
+        assert!(BackupRetention::default().expired(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_list_and_prune_backups() {
+        let tmp_dir = Path::new("/tmp/test_list_and_prune_backups");
+        let _ = fs::remove_dir_all(tmp_dir);
+        fs::create_dir_all(tmp_dir).unwrap();
+        let now = NaiveDateTime::parse_from_str("20260301120000", BACKUP_TIMESTAMP_FORMAT).unwrap();
+
+        // 同一秒内的备份追加序号，排序时排在无序号的文件之后
+        let mut paths = Vec::new();
+        for _ in 0..3 {
+            let path = backup_file_path(tmp_dir, "wallet_backup_", now).unwrap();
+            fs::write(&path, "{}").unwrap();
+            paths.push(path);
+        }
+        assert!(paths[2].ends_with("wallet_backup_20260301120000-2.json"));
+        fs::write(tmp_dir.join("wallet_backup_notes.json"), "{}").unwrap();
+        fs::write(tmp_dir.join("wallet_snapshot_20260301120000.json"), "{}").unwrap();
+
+        let backups = list_backups(tmp_dir, "wallet_backup_").unwrap();
+        let listed: Vec<PathBuf> = backups.iter().map(|b| b.path.clone()).collect();
+        assert_eq!(listed, paths);
+
+        let retention = BackupRetention {
+            keep_last: 2,
+            ..Default::default()
+        };
+        let removed = prune_backups(tmp_dir, "wallet_backup_", &retention).unwrap();
+        assert_eq!(removed, vec![paths[0].clone()]);
+        assert_eq!(list_backups(tmp_dir, "wallet_backup_").unwrap().len(), 2);
+        assert!(tmp_dir.join("wallet_backup_notes.json").exists());
+        assert!(tmp_dir.join("wallet_snapshot_20260301120000.json").exists());
+
+        // 清理后新备份的序号仍然递增
+        fs::remove_file(&paths[1]).unwrap();
+        let next = backup_file_path(tmp_dir, "wallet_backup_", now).unwrap();
+        assert!(next.ends_with("wallet_backup_20260301120000-3.json"));
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+}
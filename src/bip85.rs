@@ -0,0 +1,302 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bip39::{Language, Mnemonic};
+use bitcoin::bip32::{ChainCode, ChildNumber, DerivationPath, Fingerprint, Xpriv};
+use bitcoin::{Network, PrivateKey};
+use hmac::{Hmac, Mac};
+use secp256k1::{Secp256k1, SecretKey};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::address::{ErasingXpriv, FreeWebMovementAddress, MnemonicInfo};
+use crate::consts::{
+    BIP85_APP_BIP39, BIP85_APP_HEX, BIP85_APP_PWD_BASE64, BIP85_APP_PWD_BASE85, BIP85_APP_WIF,
+    BIP85_APP_XPRV, BIP85_HMAC_KEY, BIP85_PURPOSE,
+};
+use crate::error::AccountError;
+
+/// RFC1924 Base85 字母表，与 Python 的 base64.b85encode 一致
+const BASE85_ALPHABET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// BIP85：由主扩展私钥确定性地派生子助记词、私钥和口令
+pub struct Bip85 {
+    root: ErasingXpriv,
+}
+
+/// BIP85 规定的助记词语言编号
+pub fn language_code(language: Language) -> u32 {
+    match language {
+        Language::English => 0,
+        Language::Japanese => 1,
+        Language::Korean => 2,
+        Language::Spanish => 3,
+        Language::SimplifiedChinese => 4,
+        Language::TraditionalChinese => 5,
+        Language::French => 6,
+        Language::Italian => 7,
+        Language::Czech => 8,
+        Language::Portuguese => 9,
+    }
+}
+
+fn hardened(index: u32) -> Result<ChildNumber, AccountError> {
+    Ok(ChildNumber::from_hardened_idx(index)?)
+}
+
+fn check_range(name: &str, value: usize, min: usize, max: usize) -> Result<(), AccountError> {
+    if value < min || value > max {
+        return Err(AccountError::Bip85(format!(
+            "{} must be between {} and {}, got {}",
+            name, min, max, value
+        )));
+    }
+    Ok(())
+}
+
+fn base85_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len() / 4 * 5);
+    for chunk in data.chunks(4) {
+        let mut block = [0u8; 4];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(block);
+        let mut chars = [0u8; 5];
+        for c in chars.iter_mut().rev() {
+            *c = BASE85_ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+        // 与 b85encode 相同：末尾不足 4 字节时去掉补零对应的字符
+        let len = if chunk.len() == 4 { 5 } else { chunk.len() + 1 };
+        out.extend(chars[..len].iter().map(|&c| c as char));
+    }
+    out
+}
+
+impl Bip85 {
+    pub fn new(root: Xpriv) -> Self {
+        Bip85 {
+            root: ErasingXpriv(root),
+        }
+    }
+
+    /// 由助记词和 passphrase 生成主扩展私钥，与 `to_key_pair` 使用同一个种子
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        network: Network,
+    ) -> Result<Self, AccountError> {
+        let seed = FreeWebMovementAddress::mnemonic_to_seed(mnemonic, passphrase);
+        Ok(Bip85::new(Xpriv::new_master(network, seed.as_ref())?))
+    }
+
+    /// 钱包中不保存 passphrase，需要调用方传入
+    pub fn from_address(
+        address: &FreeWebMovementAddress,
+        passphrase: &str,
+    ) -> Result<Self, AccountError> {
+        Bip85::from_mnemonic(&address.mnemonic, passphrase, address.info.network)
+    }
+
+    /// 派生 m/83696968'/... 下的私钥 k，返回 HMAC-SHA512("bip-entropy-from-k", k)
+    pub fn derive_entropy(&self, path: &[u32]) -> Result<Zeroizing<[u8; 64]>, AccountError> {
+        let mut children = vec![hardened(BIP85_PURPOSE)?];
+        for index in path {
+            children.push(hardened(*index)?);
+        }
+        let secp = Secp256k1::signing_only();
+        let child = ErasingXpriv(
+            self.root
+                .0
+                .derive_priv(&secp, &DerivationPath::from(children))?,
+        );
+
+        let mut mac = Hmac::<Sha512>::new_from_slice(BIP85_HMAC_KEY.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(&child.0.private_key.secret_bytes());
+        let mut entropy = Zeroizing::new([0u8; 64]);
+        entropy.copy_from_slice(&mac.finalize().into_bytes());
+        Ok(entropy)
+    }
+
+    /// 子助记词：m/83696968'/39'/{language}'/{words}'/{index}'
+    pub fn mnemonic(
+        &self,
+        language: Language,
+        word_count: usize,
+        index: u32,
+    ) -> Result<Mnemonic, AccountError> {
+        if ![12, 18, 24].contains(&word_count) {
+            return Err(AccountError::Mnemonic(bip39::Error::BadWordCount(
+                word_count,
+            )));
+        }
+        let entropy = self.derive_entropy(&[
+            BIP85_APP_BIP39,
+            language_code(language),
+            word_count as u32,
+            index,
+        ])?;
+        Ok(Mnemonic::from_entropy_in(
+            language,
+            &entropy[..word_count * 4 / 3],
+        )?)
+    }
+
+    /// 子助记词的 MnemonicInfo，可直接传给 `FreeWebMovementAddress::new`
+    pub fn mnemonic_info(
+        &self,
+        language: Language,
+        word_count: usize,
+        index: u32,
+    ) -> Result<MnemonicInfo, AccountError> {
+        let mnemonic = self.mnemonic(language, word_count, index)?;
+        Ok(MnemonicInfo {
+            language,
+            word_count,
+            phrase: mnemonic.to_string().into(),
+            passphrase: String::new().into(),
+        })
+    }
+
+    /// HD-Seed WIF：m/83696968'/2'/{index}'，取熵的前 32 字节作为压缩私钥
+    pub fn wif(&self, index: u32) -> Result<PrivateKey, AccountError> {
+        let entropy = self.derive_entropy(&[BIP85_APP_WIF, index])?;
+        let key = SecretKey::from_slice(&entropy[..32])?;
+        Ok(PrivateKey::new(key, self.root.0.network))
+    }
+
+    /// XPRV：m/83696968'/32'/{index}'，前 32 字节为链码，后 32 字节为私钥
+    pub fn xprv(&self, index: u32) -> Result<Xpriv, AccountError> {
+        let entropy = self.derive_entropy(&[BIP85_APP_XPRV, index])?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&entropy[..32]);
+        Ok(Xpriv {
+            network: self.root.0.network,
+            depth: 0,
+            parent_fingerprint: Fingerprint::default(),
+            child_number: ChildNumber::from_normal_idx(0)?,
+            private_key: SecretKey::from_slice(&entropy[32..])?,
+            chain_code: ChainCode::from(chain_code),
+        })
+    }
+
+    /// HEX：m/83696968'/128169'/{num_bytes}'/{index}'，num_bytes 取 16 到 64
+    pub fn hex(&self, num_bytes: usize, index: u32) -> Result<Zeroizing<String>, AccountError> {
+        check_range("num_bytes", num_bytes, 16, 64)?;
+        let entropy = self.derive_entropy(&[BIP85_APP_HEX, num_bytes as u32, index])?;
+        Ok(Zeroizing::new(hex::encode(&entropy[..num_bytes])))
+    }
+
+    /// PWD BASE64：m/83696968'/707764'/{pwd_len}'/{index}'，pwd_len 取 20 到 86
+    pub fn password_base64(
+        &self,
+        pwd_len: usize,
+        index: u32,
+    ) -> Result<Zeroizing<String>, AccountError> {
+        check_range("pwd_len", pwd_len, 20, 86)?;
+        let entropy = self.derive_entropy(&[BIP85_APP_PWD_BASE64, pwd_len as u32, index])?;
+        let mut encoded = Zeroizing::new(STANDARD.encode(&entropy[..]));
+        encoded.truncate(pwd_len);
+        Ok(encoded)
+    }
+
+    /// PWD BASE85：m/83696968'/707785'/{pwd_len}'/{index}'，pwd_len 取 10 到 80
+    pub fn password_base85(
+        &self,
+        pwd_len: usize,
+        index: u32,
+    ) -> Result<Zeroizing<String>, AccountError> {
+        check_range("pwd_len", pwd_len, 10, 80)?;
+        let entropy = self.derive_entropy(&[BIP85_APP_PWD_BASE85, pwd_len as u32, index])?;
+        let mut encoded = Zeroizing::new(base85_encode(&entropy[..]));
+        encoded.truncate(pwd_len);
+        Ok(encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // BIP85 测试向量使用的主扩展私钥
+    const ROOT: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    fn bip85() -> Bip85 {
+        Bip85::new(Xpriv::from_str(ROOT).unwrap())
+    }
+
+    #[test]
+    fn test_bip85_vectors() {
+        let bip85 = bip85();
+        assert_eq!(
+            hex::encode(&bip85.derive_entropy(&[0, 0]).unwrap()[..]),
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+        );
+        assert_eq!(
+            bip85
+                .mnemonic(Language::English, 12, 0)
+                .unwrap()
+                .to_string(),
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+        );
+        assert_eq!(
+            bip85
+                .mnemonic(Language::English, 18, 0)
+                .unwrap()
+                .to_string(),
+            "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token"
+        );
+        assert_eq!(
+            bip85
+                .mnemonic(Language::English, 24, 0)
+                .unwrap()
+                .to_string(),
+            "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano"
+        );
+        assert_eq!(
+            bip85.wif(0).unwrap().to_wif(),
+            "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp"
+        );
+        assert_eq!(
+            bip85.xprv(0).unwrap().to_string(),
+            "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX"
+        );
+        assert_eq!(
+            bip85.hex(64, 0).unwrap().as_str(),
+            "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+        );
+        assert_eq!(
+            bip85.password_base64(21, 0).unwrap().as_str(),
+            "dKLoepugzdVJvdL56ogNV"
+        );
+        assert_eq!(
+            bip85.password_base85(12, 0).unwrap().as_str(),
+            "_s`{TW89)i4`"
+        );
+    }
+
+    #[test]
+    fn test_bip85_child_wallets() {
+        let parent = FreeWebMovementAddress::random();
+        let bip85 = Bip85::from_address(&parent, "").unwrap();
+
+        for language in [Language::English, Language::SimplifiedChinese] {
+            let mi = bip85.mnemonic_info(language, 12, 0).unwrap();
+            let child = FreeWebMovementAddress::new(mi, None);
+            assert_eq!(child.mnemonic.language(), language);
+            assert_eq!(child.mnemonic.word_count(), 12);
+            assert_ne!(child.address, parent.address);
+
+            // 相同参数总是派生出相同的子钱包，不同索引互不相同
+            let again = bip85.mnemonic(language, 12, 0).unwrap();
+            assert_eq!(again, child.mnemonic);
+            assert_ne!(bip85.mnemonic(language, 12, 1).unwrap(), child.mnemonic);
+        }
+
+        assert!(bip85.mnemonic(Language::English, 15, 0).is_err());
+        assert!(bip85.hex(15, 0).is_err());
+        assert!(bip85.password_base64(87, 0).is_err());
+        assert!(bip85.password_base85(9, 0).is_err());
+    }
+}
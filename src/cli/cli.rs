@@ -1,10 +1,48 @@
-use clap::{Parser, Subcommand};
-use zz_account::wallet::Wallet;
+use std::str::FromStr;
+
+use bip39::Language;
+use bitcoin::bip32::Xpub;
+use bitcoin::{AddressType, Network, PublicKey};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
+use zeroize::Zeroizing;
+use zz_account::address::{AddressInfo, FreeWebMovementAddress, MnemonicInfo};
+use zz_account::backup::BackupRetention;
+use zz_account::consts::{COIN_PREFIX, DICE_DEFAULT_SIDES, MNEMONIC_WORD_COUNT};
+use zz_account::ecies::{EciesCiphertext, encrypt, encrypt_to_address};
+use zz_account::entropy::{EntropyInput, collect_entropy};
+use zz_account::error::AccountError;
+use zz_account::mnemonic::{language_name, parse_language};
+use zz_account::public_address::PublicAddress;
+use zz_account::storage::{PermissionPolicy, repair_permissions};
+use zz_account::vanity::{VanityPattern, VanitySearch};
+use zz_account::wallet::{Wallet, resolve_wallet_dir};
+use zz_account::watch_only::WatchOnlyWallet;
 
 #[derive(Parser)]
 #[command(name = "zz-wallet")]
 #[command(author, version, about)]
 pub struct Cli {
+    /// 交互输入钱包口令（不回显），未设置 ZZ_WALLET_PASSWORD 时使用；设置口令后钱包文件与备份将加密保存
+    #[arg(long, global = true)]
+    pub ask_password: bool,
+
+    /// 交互输入创建钱包时使用的 BIP39 passphrase（不回显），派生 HD 地址时需要；也可用 ZZ_WALLET_PASSPHRASE
+    #[arg(long, global = true)]
+    pub ask_passphrase: bool,
+
+    /// 已废弃：命令行参数会留在 shell 历史和进程列表中，传入时直接报错
+    #[arg(long, global = true, hide = true)]
+    pub password: Option<String>,
+
+    /// 已废弃，同 --password
+    #[arg(long, global = true, hide = true)]
+    pub passphrase: Option<String>,
+
+    /// 钱包文件权限过宽时拒绝读取，而不是只给出警告
+    #[arg(long, global = true, env = "ZZ_WALLET_STRICT_PERMISSIONS")]
+    pub strict_permissions: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -35,18 +73,148 @@ pub enum Commands {
         #[arg(long)]
         file: Option<String>,
     },
+    Receive {
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    Change {
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    Xpub {
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 只读钱包：给定 xpub 时新建，否则打开已有文件，输出下一个收款地址
+    Watch {
+        #[arg(long)]
+        xpub: Option<String>,
+        /// 新建时的地址类型，须与导出 xpub 的账户一致：p2pkh、p2sh、p2wpkh、p2tr
+        #[arg(long, default_value = "p2pkh")]
+        address_type: String,
+        /// 新建时的网络：bitcoin、testnet、signet、regtest，默认按 xpub 判断主网或 testnet
+        #[arg(long)]
+        network: Option<String>,
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 将钱包助记词翻译为另一种语言（english、simplified-chinese、traditional-chinese 等）
+    Translate {
+        language: String,
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 搜索靓号地址并保存为新钱包；prefix / suffix / regex 匹配 FWMC:Zz: 之后的部分
+    Vanity {
+        #[arg(long, group = "pattern")]
+        prefix: Option<String>,
+        #[arg(long, group = "pattern")]
+        suffix: Option<String>,
+        #[arg(long, group = "pattern")]
+        regex: Option<String>,
+        /// 地址类型：p2pkh、p2sh、p2wpkh、p2tr
+        #[arg(long, default_value = "p2pkh")]
+        address_type: String,
+        /// 工作线程数，默认使用全部 CPU 核心
+        #[arg(long)]
+        threads: Option<usize>,
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 由骰子、硬币或十六进制熵新建钱包，可混入系统随机数
+    Import {
+        #[arg(long, group = "entropy")]
+        dice: Option<String>,
+        #[arg(long, group = "entropy")]
+        coins: Option<String>,
+        #[arg(long, group = "entropy")]
+        hex: Option<String>,
+        #[arg(long, default_value_t = DICE_DEFAULT_SIDES)]
+        sides: u32,
+        #[arg(long, default_value_t = MNEMONIC_WORD_COUNT)]
+        words: usize,
+        /// 与系统随机数异或
+        #[arg(long)]
+        mix: bool,
+        #[arg(long, default_value = "english")]
+        language: String,
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 导出钱包助记词的原始熵（十六进制），供离线审计
+    Entropy {
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 加密备注给对方：--to 为十六进制公钥或 P2tr 的 FWMC:Zz: 地址，输出 JSON 密文
+    Encrypt {
+        #[arg(long)]
+        to: String,
+        message: String,
+    },
+    /// 用钱包私钥解密 JSON 密文
+    Decrypt {
+        ciphertext: String,
+        #[arg(long)]
+        dir: Option<String>,
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// 管理钱包中的多个账户
+    Account {
+        #[command(subcommand)]
+        action: AccountAction,
+        #[arg(long, global = true)]
+        dir: Option<String>,
+        #[arg(long, global = true)]
+        file: Option<String>,
+    },
+    /// 将钱包目录设为 0700、其中的文件设为 0600
+    FixPermissions {
+        #[arg(long)]
+        dir: Option<String>,
+    },
+    /// 生成带时间戳的备份；给出任一 --keep-* 时按保留策略清理同目录中的旧备份
     Backup {
         #[arg(long)]
         path: Option<String>,
+        /// 保留最近的 N 个备份
+        #[arg(long)]
+        keep_last: Option<usize>,
+        /// 保留最近 N 天中每天最新的备份
+        #[arg(long)]
+        keep_daily: Option<usize>,
+        /// 保留最近 N 周中每周最新的备份
+        #[arg(long)]
+        keep_weekly: Option<usize>,
         #[arg(long)]
         dir: Option<String>,
         #[arg(long)]
         file: Option<String>,
     },
+    /// 从备份恢复；--snapshot 先为当前钱包生成快照，可用 --path <快照> 撤销
     Recovery {
         #[arg(long)]
         path: Option<String>,
         #[arg(long)]
+        snapshot: bool,
+        #[arg(long)]
         dir: Option<String>,
         #[arg(long)]
         file: Option<String>,
@@ -54,20 +222,212 @@ pub enum Commands {
     Repl,
 }
 
+#[derive(Subcommand)]
+pub enum AccountAction {
+    /// 列出全部账户，默认账户以 * 标记
+    List,
+    /// 添加使用新随机助记词的账户
+    Add {
+        label: String,
+        #[arg(long, default_value = "p2pkh")]
+        address_type: String,
+    },
+    /// 用已有账户的助记词派生新的 HD 账户
+    Derive {
+        label: String,
+        #[arg(long)]
+        account: u32,
+        /// 来源账户，默认为当前默认账户
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long, default_value = "p2pkh")]
+        address_type: String,
+    },
+    Remove {
+        label: String,
+    },
+    Rename {
+        label: String,
+        new_label: String,
+    },
+    Default {
+        label: String,
+    },
+}
+
+fn run_account_action(
+    wallet: &mut Wallet,
+    action: AccountAction,
+    passphrase: &str,
+) -> Result<(), AccountError> {
+    let parse_type = |address_type: &str| {
+        AddressType::from_str(address_type)
+            .map_err(|_| AccountError::UnsupportedAddressType(address_type.to_string()))
+    };
+    match action {
+        AccountAction::List => {
+            for account in wallet.accounts() {
+                let marker = if account.label == wallet.default_account().label {
+                    "*"
+                } else {
+                    " "
+                };
+                println!("{} {}\t{}", marker, account.label, account.address);
+            }
+            return Ok(());
+        }
+        AccountAction::Add {
+            label,
+            address_type,
+        } => {
+            let mnemonic_info = MnemonicInfo {
+                language: Language::English,
+                word_count: MNEMONIC_WORD_COUNT,
+                phrase: String::new().into(),
+                passphrase: String::new().into(),
+            };
+            let info = AddressInfo::new(Network::Bitcoin, parse_type(&address_type)?);
+            let address = FreeWebMovementAddress::try_new(mnemonic_info, Some(info))?;
+            println!("{}", wallet.add_account(&label, address)?.address);
+        }
+        AccountAction::Derive {
+            label,
+            account,
+            from,
+            address_type,
+        } => {
+            let from = from.unwrap_or_else(|| wallet.default_account().label.clone());
+            let address_type = parse_type(&address_type)?;
+            let derived =
+                wallet.derive_account(&label, &from, account, address_type, passphrase)?;
+            println!("{}", derived.address);
+        }
+        AccountAction::Remove { label } => {
+            wallet.remove_account(&label)?;
+        }
+        AccountAction::Rename { label, new_label } => {
+            wallet.rename_account(&label, &new_label)?;
+        }
+        AccountAction::Default { label } => {
+            wallet.set_default_account(&label)?;
+        }
+    }
+    wallet.try_save()
+}
+
+fn watch_only_from_xpub(
+    xpub: &str,
+    address_type: &str,
+    network: Option<&str>,
+    dir: Option<&str>,
+    file: Option<&str>,
+) -> Result<WatchOnlyWallet, AccountError> {
+    let address_type = AddressType::from_str(address_type)
+        .map_err(|_| AccountError::UnsupportedAddressType(address_type.to_string()))?;
+    let network = match network {
+        Some(network) => Network::from_str(network)
+            .map_err(|_| AccountError::NetworkMismatch(format!("unknown network {}", network)))?,
+        None => Xpub::from_str(xpub)?.network,
+    };
+    WatchOnlyWallet::from_xpub(xpub, address_type, network, dir, file)
+}
+
+fn open_wallet(
+    dir: Option<&str>,
+    file: Option<&str>,
+    password: Option<&str>,
+    policy: PermissionPolicy,
+) -> Option<Wallet> {
+    match Wallet::open_with_policy(dir, file, password, policy) {
+        Ok(wallet) => Some(wallet),
+        Err(e) => {
+            tracing::error!("open wallet failed: {}", e);
+            None
+        }
+    }
+}
+
+/// 优先读取环境变量；ask 为 true 且环境变量未设置时在终端提示输入，不回显
+fn read_secret(env_var: &str, ask: bool, prompt: &str) -> Option<Zeroizing<String>> {
+    if let Ok(value) = std::env::var(env_var) {
+        return Some(Zeroizing::new(value));
+    }
+    if !ask {
+        return None;
+    }
+    match rpassword::prompt_password(prompt) {
+        Ok(value) => Some(Zeroizing::new(value)),
+        Err(e) => Cli::command()
+            .error(
+                ErrorKind::Io,
+                format!("failed to read {}: {}", prompt.trim_end_matches(": "), e),
+            )
+            .exit(),
+    }
+}
+
 pub fn run_cli(cli: Cli) {
+    for (value, flag, env_var, ask) in [
+        (
+            &cli.password,
+            "--password",
+            "ZZ_WALLET_PASSWORD",
+            "--ask-password",
+        ),
+        (
+            &cli.passphrase,
+            "--passphrase",
+            "ZZ_WALLET_PASSPHRASE",
+            "--ask-passphrase",
+        ),
+    ] {
+        if value.is_some() {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "{} is no longer accepted on the command line because it leaks into shell history and process listings; set {} or use {}",
+                        flag, env_var, ask
+                    ),
+                )
+                .exit();
+        }
+    }
+    let password = read_secret("ZZ_WALLET_PASSWORD", cli.ask_password, "wallet password: ");
+    let password = password.as_deref().map(String::as_str);
+    let passphrase = read_secret(
+        "ZZ_WALLET_PASSPHRASE",
+        cli.ask_passphrase,
+        "BIP39 passphrase: ",
+    );
+    let passphrase = passphrase.as_deref().map_or("", String::as_str);
+    let policy = if cli.strict_permissions {
+        PermissionPolicy::Strict
+    } else {
+        PermissionPolicy::Warn
+    };
     match cli.command {
         Commands::New { dir, file } => {
-            let wallet = Wallet::new(dir.as_deref(), file.as_deref());
-            println!("{}", wallet.address.to_string());
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            println!("{}", wallet.address());
         }
 
         Commands::Show { dir, file } => {
-            let wallet = Wallet::new(dir.as_deref(), file.as_deref());
-            println!("{}", wallet.address.to_string());
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            println!("{}", wallet.address());
         }
 
         Commands::Save { dir, file } => {
-            let wallet = Wallet::new(dir.as_deref(), file.as_deref());
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
             match wallet.save() {
                 Ok(_) => println!("saved: {}", wallet.to_absolute_path()),
                 Err(e) => tracing::error!("save failed: {}", e),
@@ -75,31 +435,310 @@ pub fn run_cli(cli: Cli) {
         }
 
         Commands::Load { dir, file } => {
-            let mut wallet = Wallet::new(dir.as_deref(), file.as_deref());
+            let Some(mut wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
             match wallet.load() {
-                Ok(_) => println!("{}", wallet.address.to_string()),
+                Ok(_) => println!("{}", wallet.address()),
                 Err(e) => tracing::error!("load failed: {}", e),
             }
         }
 
-        Commands::Backup { path, dir, file } => {
-            let wallet = Wallet::new(dir.as_deref(), file.as_deref());
+        Commands::Receive { dir, file } => {
+            let Some(mut wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            match wallet.next_receive_address(passphrase) {
+                Ok(address) => println!("{}", address),
+                Err(e) => tracing::error!("receive failed: {}", e),
+            }
+        }
+
+        Commands::Change { dir, file } => {
+            let Some(mut wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            match wallet.next_change_address(passphrase) {
+                Ok(address) => println!("{}", address),
+                Err(e) => tracing::error!("change failed: {}", e),
+            }
+        }
+
+        Commands::Xpub { dir, file } => {
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            match wallet.account_xpub(passphrase) {
+                Ok(xpub) => println!("{}", xpub),
+                Err(e) => tracing::error!("xpub failed: {}", e),
+            }
+        }
+
+        Commands::Watch {
+            xpub,
+            address_type,
+            network,
+            dir,
+            file,
+        } => {
+            let result = match xpub {
+                Some(xpub) => watch_only_from_xpub(
+                    &xpub,
+                    &address_type,
+                    network.as_deref(),
+                    dir.as_deref(),
+                    file.as_deref(),
+                ),
+                None => WatchOnlyWallet::open_with_policy(dir.as_deref(), file.as_deref(), policy),
+            };
+            match result.and_then(|mut wallet| {
+                let address = wallet.next_receive_address()?;
+                Ok(wallet.to_prefixed(&address))
+            }) {
+                Ok(address) => println!("{}", address),
+                Err(e) => tracing::error!("watch failed: {}", e),
+            }
+        }
+
+        Commands::Translate {
+            language,
+            dir,
+            file,
+        } => {
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            match parse_language(&language)
+                .and_then(|language| wallet.address().translate_mnemonic(language, passphrase))
+            {
+                Ok(translation) => {
+                    println!("{}", translation.mnemonic);
+                    if translation.address_changed {
+                        println!(
+                            "WARNING: BIP39 seeds are derived from the words, so this phrase restores a different wallet ({}).",
+                            translation.address
+                        );
+                        println!(
+                            "To recover {} from it, translate it back to {} before restoring.",
+                            wallet.address(),
+                            language_name(wallet.address().mnemonic.language())
+                        );
+                    } else {
+                        println!("addresses are unchanged: {}", wallet.address());
+                    }
+                }
+                Err(e) => tracing::error!("translate failed: {}", e),
+            }
+        }
+
+        Commands::Vanity {
+            prefix,
+            suffix,
+            regex,
+            address_type,
+            threads,
+            dir,
+            file,
+        } => {
+            let pattern = match (prefix, suffix, regex) {
+                (Some(prefix), _, _) => VanityPattern::Prefix(prefix),
+                (_, Some(suffix), _) => VanityPattern::Suffix(suffix),
+                (_, _, Some(regex)) => VanityPattern::Regex(regex),
+                _ => {
+                    println!("one of --prefix, --suffix or --regex is required");
+                    return;
+                }
+            };
+            let search = AddressType::from_str(&address_type)
+                .map_err(|_| AccountError::UnsupportedAddressType(address_type))
+                .and_then(|address_type| {
+                    VanitySearch::new(pattern, AddressInfo::new(Network::Bitcoin, address_type))
+                });
+            let mut search = match search {
+                Ok(search) => search,
+                Err(e) => {
+                    tracing::error!("vanity failed: {}", e);
+                    return;
+                }
+            };
+            if let Some(threads) = threads {
+                search = search.threads(threads);
+            }
+            match search.run(|progress| {
+                let eta = progress
+                    .eta
+                    .map_or("unknown".to_string(), |eta| format!("{}s", eta.as_secs()));
+                eprintln!(
+                    "{} attempts, {:.0}/s, eta {}",
+                    progress.attempts, progress.rate, eta
+                );
+            }) {
+                Ok(Some(address)) => {
+                    match Wallet::create(address, dir.as_deref(), file.as_deref(), password) {
+                        Ok(wallet) => {
+                            println!("{}", wallet.address());
+                            println!("saved: {}", wallet.to_absolute_path());
+                        }
+                        Err(e) => tracing::error!("save failed: {}", e),
+                    }
+                }
+                Ok(None) => println!("cancelled"),
+                Err(e) => tracing::error!("vanity failed: {}", e),
+            }
+        }
+
+        Commands::Import {
+            dice,
+            coins,
+            hex,
+            sides,
+            words,
+            mix,
+            language,
+            dir,
+            file,
+        } => {
+            let input = match (&dice, &coins, &hex) {
+                (Some(rolls), _, _) => EntropyInput::Dice { rolls, sides },
+                (_, Some(flips), _) => EntropyInput::Coins(flips),
+                (_, _, Some(hex)) => EntropyInput::Hex(hex),
+                _ => {
+                    println!("one of --dice, --coins or --hex is required");
+                    return;
+                }
+            };
+            let result = parse_language(&language).and_then(|language| {
+                let entropy = collect_entropy(input, words, mix)?;
+                let address = FreeWebMovementAddress::from_entropy(&entropy, language, "", None)?;
+                Wallet::create(address, dir.as_deref(), file.as_deref(), password)
+            });
+            match result {
+                Ok(wallet) => {
+                    println!("{}", wallet.address());
+                    println!("saved: {}", wallet.to_absolute_path());
+                }
+                Err(e) => tracing::error!("import failed: {}", e),
+            }
+        }
+
+        Commands::Entropy { dir, file } => {
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            println!("{}", *wallet.address().entropy_hex());
+        }
+
+        Commands::Encrypt { to, message } => {
+            let result = if to.starts_with(COIN_PREFIX) {
+                PublicAddress::from_str(&to)
+                    .and_then(|address| encrypt_to_address(&address, message.as_bytes()))
+            } else {
+                PublicKey::from_str(&to)
+                    .map_err(AccountError::from)
+                    .and_then(|public_key| encrypt(&public_key, message.as_bytes()))
+            };
+            match result.and_then(|ciphertext| ciphertext.to_json()) {
+                Ok(json) => println!("{}", json),
+                Err(e) => tracing::error!("encrypt failed: {}", e),
+            }
+        }
+
+        Commands::Decrypt {
+            ciphertext,
+            dir,
+            file,
+        } => {
+            let Some(wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            match EciesCiphertext::from_json(&ciphertext)
+                .and_then(|ciphertext| wallet.address().decrypt(&ciphertext))
+            {
+                Ok(plaintext) => println!("{}", String::from_utf8_lossy(&plaintext)),
+                Err(e) => tracing::error!("decrypt failed: {}", e),
+            }
+        }
+
+        Commands::Account { action, dir, file } => {
+            let Some(mut wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            if let Err(e) = run_account_action(&mut wallet, action, passphrase) {
+                tracing::error!("account failed: {}", e);
+            }
+        }
+
+        Commands::FixPermissions { dir } => {
+            match repair_permissions(&resolve_wallet_dir(dir.as_deref())) {
+                Ok(repaired) if repaired.is_empty() => println!("permissions are already private"),
+                Ok(repaired) => {
+                    for path in repaired {
+                        println!("repaired: {}", path.display());
+                    }
+                }
+                Err(e) => tracing::error!("fix permissions failed: {}", e),
+            }
+        }
+
+        Commands::Backup {
+            path,
+            keep_last,
+            keep_daily,
+            keep_weekly,
+            dir,
+            file,
+        } => {
+            let Some(mut wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            if keep_last.is_some() || keep_daily.is_some() || keep_weekly.is_some() {
+                wallet.set_backup_retention(Some(BackupRetention {
+                    keep_last: keep_last.unwrap_or(0),
+                    keep_daily: keep_daily.unwrap_or(0),
+                    keep_weekly: keep_weekly.unwrap_or(0),
+                }));
+            }
             match wallet.backup(path.as_deref()) {
                 Ok(backup_path) => println!("backup: {}", backup_path),
                 Err(e) => tracing::error!("backup failed: {}", e),
             }
         }
 
-        Commands::Recovery { path, dir, file } => {
-            let mut wallet = Wallet::new(dir.as_deref(), file.as_deref());
-            match wallet.recovery(path.as_deref()) {
-                Ok(_) => println!("{}", wallet.address.to_string()),
+        Commands::Recovery {
+            path,
+            snapshot,
+            dir,
+            file,
+        } => {
+            let Some(mut wallet) = open_wallet(dir.as_deref(), file.as_deref(), password, policy)
+            else {
+                return;
+            };
+            wallet.set_auto_backup(snapshot);
+            match wallet.restore(path.as_deref()) {
+                Ok(_) => {
+                    if snapshot
+                        && let Ok(Some(path)) = wallet.snapshots().map(|s| s.last().cloned())
+                    {
+                        println!("snapshot: {}", path.display());
+                    }
+                    println!("{}", wallet.address());
+                }
                 Err(e) => tracing::error!("recovery failed: {}", e),
             }
         }
 
         Commands::Repl => {
-            crate::repl::run_repl();
+            crate::repl::run_repl(password, passphrase, policy);
         }
     }
 }
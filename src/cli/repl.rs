@@ -1,11 +1,22 @@
+use bip39::Language;
 use std::io::{self, Write};
+use zz_account::mnemonic::{parse_language, validate_mnemonic};
+use zz_account::storage::PermissionPolicy;
 use zz_account::wallet::Wallet;
 
-pub fn run_repl() {
-    let mut wallet = Wallet::new(None, None);
+pub fn run_repl(password: Option<&str>, passphrase: &str, policy: PermissionPolicy) {
+    let mut wallet = match Wallet::open_with_policy(None, None, password, policy) {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            tracing::error!("open wallet failed: {}", e);
+            return;
+        }
+    };
 
     println!("zz-wallet repl");
-    println!("commands: show | save | load | backup [path] | recovery [path] | exit");
+    println!(
+        "commands: show | accounts | use <label> | save | load | receive | change | backup [path] | recovery [path] | check [--lang <language>] <words...> | exit"
+    );
 
     loop {
         print!("> ");
@@ -16,28 +27,50 @@ pub fn run_repl() {
             continue;
         }
 
-        let parts: Vec<_> = line.trim().split_whitespace().collect();
+        let parts: Vec<_> = line.split_whitespace().collect();
         if parts.is_empty() {
             continue;
         }
 
         match parts[0] {
             "show" => {
-                println!("{}", wallet.address.to_string());
+                println!("{}", wallet.address());
+            }
+
+            "accounts" => {
+                for account in wallet.accounts() {
+                    println!("{}\t{}", account.label, account.address);
+                }
             }
 
+            "use" => match parts.get(1).map(|label| wallet.set_default_account(label)) {
+                Some(Ok(_)) => println!("{}", wallet.address()),
+                Some(Err(e)) => println!("{}", e),
+                None => println!("usage: use <label>"),
+            },
+
             "save" => match wallet.save() {
                 Ok(_) => println!("saved"),
                 Err(e) => tracing::error!("save failed: {}", e),
             },
 
             "load" => match wallet.load() {
-                Ok(_) => println!("{}", wallet.address.to_string()),
+                Ok(_) => println!("{}", wallet.address()),
                 Err(e) => tracing::error!("load failed: {}", e),
             },
 
+            "receive" => match wallet.next_receive_address(passphrase) {
+                Ok(address) => println!("{}", address),
+                Err(e) => tracing::error!("receive failed: {}", e),
+            },
+
+            "change" => match wallet.next_change_address(passphrase) {
+                Ok(address) => println!("{}", address),
+                Err(e) => tracing::error!("change failed: {}", e),
+            },
+
             "backup" => {
-                let path = parts.get(1).map(|s| *s);
+                let path = parts.get(1).copied();
                 match wallet.backup(path) {
                     Ok(p) => println!("backup: {}", p),
                     Err(e) => tracing::error!("backup failed: {}", e),
@@ -45,13 +78,31 @@ pub fn run_repl() {
             }
 
             "recovery" => {
-                let path = parts.get(1).map(|s| *s);
-                match wallet.recovery(path) {
-                    Ok(_) => println!("{}", wallet.address.to_string()),
+                let path = parts.get(1).copied();
+                match wallet.restore(path) {
+                    Ok(_) => println!("{}", wallet.address()),
                     Err(e) => tracing::error!("recovery failed: {}", e),
                 }
             }
 
+            "check" => {
+                let (language, words) = match parts.get(1) {
+                    Some(&"--lang") => match parts.get(2).map(|l| parse_language(l)) {
+                        Some(Ok(language)) => (language, &parts[3..]),
+                        Some(Err(e)) => {
+                            println!("{}", e);
+                            continue;
+                        }
+                        None => {
+                            println!("usage: check [--lang <language>] <words...>");
+                            continue;
+                        }
+                    },
+                    _ => (Language::English, &parts[1..]),
+                };
+                println!("{}", validate_mnemonic(&words.join(" "), language));
+            }
+
             "exit" | "quit" => {
                 break;
             }
@@ -6,6 +6,10 @@ pub const COIN_SYMBOL: &str = "FWMC-ZZ";
 pub const COIN_DECIMALS: u8 = 8; // 1 Zz = 0.00000001 USD
 
 pub const DERIVATION_PATH: &str = "m/44'/1010086'/0'/0/0"; // 默认的派生路径
+pub const TAPROOT_DERIVATION_PATH: &str = "m/86'/1010086'/0'/0/0"; // BIP86 Taproot 默认派生路径
+pub const BIP44_PURPOSE: u32 = 44; // BIP44 purpose
+pub const BIP86_PURPOSE: u32 = 86; // BIP86 purpose (Taproot)
+pub const COIN_TYPE: u32 = 1010086; // 派生路径中的 coin_type
 pub const MNEMONIC_STR: &str = "mnemonic"; // 默认的Mnemonic字符串前缀
 pub const MNEMONIC_WORD_COUNT: usize = 24; // 默认的Word数量
 pub const MNEMONIC_SEED_SIZE: usize = 64; // 默认的种子大小
@@ -14,3 +18,77 @@ pub const MNEMONIC_SEED_ROUNDS: u32 = 2048; // 默认的种子的轮数
 pub const COIN_PREFIX: &str = "FWMC:Zz"; // 加密币前缀: FWM for Free Web Movement, Zz for Zero Trust, Zero Governance
 pub const DEFAULT_WALLET_DIR: &str = ".free-web-movement/account/wallets";
 pub const DEFAULT_WALLET_FILE: &str = "wallet.json";
+pub const WALLET_SCHEMA_VERSION: u32 = 2; // 钱包文件格式版本：0 为旧的单地址格式，1 为无版本号的多账户格式
+pub const WALLET_DIR_MODE: u32 = 0o700; // 钱包目录权限，仅所有者可访问
+pub const WALLET_FILE_MODE: u32 = 0o600; // 钱包与备份文件权限，仅所有者可读写
+pub const DEFAULT_ACCOUNT_LABEL: &str = "default"; // 新钱包及旧格式钱包迁移后的账户标签
+pub const WALLET_LOCK_TIMEOUT_MS: u64 = 10_000; // 等待钱包锁的默认超时（毫秒）
+pub const WALLET_LOCK_RETRY_MS: u64 = 50; // 钱包锁被占用时的重试间隔（毫秒）
+pub const BACKUP_PREFIX: &str = "wallet_backup_"; // 手动备份文件名前缀
+pub const SNAPSHOT_PREFIX: &str = "wallet_snapshot_"; // 破坏性操作前自动快照的文件名前缀
+pub const LEGACY_JNI_BACKUP_PREFIX: &str = "backup_"; // 旧版 JNI 备份文件名前缀，仅用于恢复
+pub const DEFAULT_BACKUP_KEEP_LAST: usize = 10; // 无法配置保留策略时（如 JNI）保留的最近备份数
+pub const DEFAULT_BACKUP_KEEP_DAILY: usize = 7; // 同上，保留最近几天每天最新的备份
+pub const DEFAULT_BACKUP_KEEP_WEEKLY: usize = 4; // 同上，保留最近几周每周最新的备份
+pub const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d%H%M%S"; // 备份文件名中的本地时间格式
+pub const DEFAULT_WATCH_ONLY_FILE: &str = "watch_only.json"; // 只读钱包默认文件名
+
+pub const KEYSTORE_VERSION: u32 = 1; // 加密钱包文件格式版本
+pub const KEYSTORE_KDF: &str = "argon2id"; // 口令派生算法
+pub const KEYSTORE_CIPHER: &str = "xchacha20poly1305"; // 对称加密算法
+pub const KEYSTORE_KDF_M_COST: u32 = 19 * 1024; // Argon2 内存开销 (KiB)
+pub const KEYSTORE_KDF_T_COST: u32 = 2; // Argon2 迭代次数
+pub const KEYSTORE_KDF_P_COST: u32 = 1; // Argon2 并行度
+pub const KEYSTORE_KDF_MAX_M_COST: u32 = 1024 * 1024; // 解密时允许的最大内存开销 (KiB)，即 1 GiB
+pub const KEYSTORE_KDF_MAX_T_COST: u32 = 10; // 解密时允许的最大迭代次数
+pub const KEYSTORE_KDF_MAX_P_COST: u32 = 16; // 解密时允许的最大并行度
+pub const KEYSTORE_SALT_SIZE: usize = 16;
+pub const KEYSTORE_KEY_SIZE: usize = 32;
+
+pub const MULTISIG_MAX_KEYS: usize = 15; // P2SH 赎回脚本 520 字节限制下的最大公钥数
+
+pub const BIP137_HEADER_P2PKH_UNCOMPRESSED: u8 = 27; // BIP137 签名头：未压缩公钥 P2PKH
+pub const BIP137_HEADER_P2PKH: u8 = 31; // BIP137 签名头：压缩公钥 P2PKH
+pub const BIP137_HEADER_P2SH_P2WPKH: u8 = 35; // BIP137 签名头：P2SH-P2WPKH
+pub const BIP137_HEADER_P2WPKH: u8 = 39; // BIP137 签名头：P2WPKH
+
+pub const SCHNORR_MESSAGE_TAG: &str = "FWMC-ZZ/message"; // BIP340 消息签名的 tagged hash 标签
+
+pub const BIP85_PURPOSE: u32 = 83696968; // BIP85 派生路径的 purpose
+pub const BIP85_HMAC_KEY: &str = "bip-entropy-from-k"; // BIP85 熵的 HMAC-SHA512 密钥
+pub const BIP85_APP_BIP39: u32 = 39; // BIP85 应用：子助记词
+pub const BIP85_APP_WIF: u32 = 2; // BIP85 应用：WIF 私钥
+pub const BIP85_APP_XPRV: u32 = 32; // BIP85 应用：扩展私钥
+pub const BIP85_APP_HEX: u32 = 128169; // BIP85 应用：十六进制熵
+pub const BIP85_APP_PWD_BASE64: u32 = 707764; // BIP85 应用：Base64 口令
+pub const BIP85_APP_PWD_BASE85: u32 = 707785; // BIP85 应用：Base85 口令
+
+pub const SLIP39_RADIX_BITS: usize = 10; // SLIP-39 每个单词表示的位数
+pub const SLIP39_CHECKSUM_WORDS: usize = 3; // RS1024 校验和单词数
+pub const SLIP39_DIGEST_BYTES: usize = 4; // 分片摘要长度
+pub const SLIP39_DIGEST_INDEX: u8 = 254; // 摘要分片的 x 坐标
+pub const SLIP39_SECRET_INDEX: u8 = 255; // 秘密分片的 x 坐标
+pub const SLIP39_MAX_SHARE_COUNT: usize = 16; // 组数与每组分片数的上限
+pub const SLIP39_MIN_SECRET_BYTES: usize = 16; // 主秘密最短 128 位
+pub const SLIP39_BASE_ITERATION_COUNT: u32 = 10000; // Feistel 轮函数 PBKDF2 基础迭代次数
+pub const SLIP39_ROUND_COUNT: u8 = 4; // Feistel 轮数
+pub const SLIP39_ITERATION_EXPONENT: u8 = 1; // 默认迭代指数
+pub const SLIP39_CUSTOMIZATION: &str = "shamir"; // 不可扩展分片的定制字符串
+pub const SLIP39_CUSTOMIZATION_EXTENDABLE: &str = "shamir_extendable"; // 可扩展分片的定制字符串
+
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24]; // BIP39 允许的单词数
+pub const MNEMONIC_SUGGESTION_LIMIT: usize = 5; // 每个未知单词最多给出的建议数
+pub const MNEMONIC_MAX_EDIT_DISTANCE: usize = 2; // 编辑距离建议的最大距离
+
+pub const BASE58_CHARSET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"; // Base58 字符集
+pub const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l"; // Bech32 数据部分字符集
+pub const BASE58_ADDRESS_LENGTH: usize = 34; // P2PKH / P2SH 地址的常见长度
+pub const VANITY_PROGRESS_INTERVAL_MS: u64 = 1000; // 靓号搜索进度回调的间隔
+
+pub const DICE_DEFAULT_SIDES: u32 = 6; // 默认骰子面数
+pub const DICE_MAX_SIDES: u32 = 256; // 支持的最大骰子面数
+
+pub const ECIES_VERSION: u32 = 1; // ECIES 密文格式版本
+pub const ECIES_KDF: &str = "hkdf-sha256"; // 共享密钥派生算法
+pub const ECIES_CIPHER: &str = "xchacha20poly1305"; // 对称加密算法
+pub const ECIES_INFO: &str = "FWMC-ZZ/ecies"; // HKDF 的 info 参数
@@ -0,0 +1,256 @@
+use bitcoin::PublicKey;
+use bitcoin::address::AddressType;
+use bitcoin::key::TapTweak;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{Keypair, Parity, Secp256k1, SecretKey, XOnlyPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::address::FreeWebMovementAddress;
+use crate::consts::{ECIES_CIPHER, ECIES_INFO, ECIES_KDF, ECIES_VERSION, KEYSTORE_KEY_SIZE};
+use crate::error::AccountError;
+use crate::public_address::PublicAddress;
+
+fn ecies_error<E: ToString>(e: E) -> AccountError {
+    AccountError::Ecies(e.to_string())
+}
+
+/// ECDH 共享密钥：共享点压缩编码的 SHA-256
+pub fn ecdh(secret_key: &SecretKey, public_key: &secp256k1::PublicKey) -> Zeroizing<[u8; 32]> {
+    Zeroizing::new(SharedSecret::new(public_key, secret_key).secret_bytes())
+}
+
+/// 由共享密钥派生对称密钥，salt 绑定临时公钥与收件人公钥
+fn derive_key(
+    shared: &[u8; 32],
+    ephemeral_key: &[u8],
+    recipient: &[u8],
+) -> Result<Zeroizing<[u8; KEYSTORE_KEY_SIZE]>, AccountError> {
+    let salt = [ephemeral_key, recipient].concat();
+    let mut key = Zeroizing::new([0u8; KEYSTORE_KEY_SIZE]);
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(ECIES_INFO.as_bytes(), key.as_mut())
+        .map_err(ecies_error)?;
+    Ok(key)
+}
+
+/// ECIES 密文：临时密钥 ECDH + HKDF-SHA256 + XChaCha20-Poly1305
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EciesCiphertext {
+    pub version: u32,
+    pub kdf: String,
+    pub cipher: String,
+    /// 临时公钥（压缩格式，十六进制）
+    pub ephemeral_key: String,
+    /// 收件人公钥（压缩格式，十六进制），解密时用来选择私钥
+    pub recipient: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl EciesCiphertext {
+    pub fn from_json(json: &str) -> Result<Self, AccountError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, AccountError> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// 加密给收件人公钥
+pub fn encrypt(recipient: &PublicKey, plaintext: &[u8]) -> Result<EciesCiphertext, AccountError> {
+    let secp = Secp256k1::new();
+    let (mut ephemeral_secret, ephemeral_public) = secp.generate_keypair(&mut OsRng);
+    let ephemeral_key = ephemeral_public.serialize();
+    let recipient_key = recipient.inner.serialize();
+
+    let shared = ecdh(&ephemeral_secret, &recipient.inner);
+    ephemeral_secret.non_secure_erase();
+    let key = derive_key(&shared, &ephemeral_key, &recipient_key)?;
+    let cipher = XChaCha20Poly1305::new((&*key).into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let aad = [&ephemeral_key[..], &recipient_key[..]].concat();
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &aad,
+            },
+        )
+        .map_err(ecies_error)?;
+
+    Ok(EciesCiphertext {
+        version: ECIES_VERSION,
+        kdf: ECIES_KDF.to_string(),
+        cipher: ECIES_CIPHER.to_string(),
+        ephemeral_key: hex::encode(ephemeral_key),
+        recipient: hex::encode(recipient_key),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+impl PublicAddress {
+    /// 地址中可用于加密的公钥：只有 P2tr 地址直接包含（tweak 后的）公钥，其余类型只有哈希
+    pub fn encryption_key(&self) -> Result<PublicKey, AccountError> {
+        if self.address_type != AddressType::P2tr {
+            return Err(ecies_error(format!(
+                "{} addresses only contain a key hash, ask the recipient for their public key",
+                self.address_type
+            )));
+        }
+        let script = self.address.script_pubkey();
+        let x_only = XOnlyPublicKey::from_slice(&script.as_bytes()[2..])?;
+        Ok(PublicKey::new(x_only.public_key(Parity::Even)))
+    }
+}
+
+/// 加密给 `FWMC:Zz:` 地址，仅支持 P2tr
+pub fn encrypt_to_address(
+    address: &PublicAddress,
+    plaintext: &[u8],
+) -> Result<EciesCiphertext, AccountError> {
+    encrypt(&address.encryption_key()?, plaintext)
+}
+
+impl FreeWebMovementAddress {
+    /// 与对方公钥的 ECDH 共享密钥，双方计算结果相同
+    pub fn shared_secret(&self, public_key: &PublicKey) -> Zeroizing<[u8; 32]> {
+        ecdh(&self.private_key.inner, &public_key.inner)
+    }
+
+    /// P2tr 输出密钥对应的私钥，调整为偶数 y 坐标以匹配 x-only 公钥
+    fn taproot_output_secret(&self) -> SecretKey {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_secret_key(&secp, &self.private_key.inner);
+        let tweaked = keypair.tap_tweak(&secp, None).to_inner();
+        let (_, parity) = tweaked.x_only_public_key();
+        let secret = tweaked.secret_key();
+        if parity == Parity::Odd {
+            secret.negate()
+        } else {
+            secret
+        }
+    }
+
+    /// 解密发给本地址公钥或 P2tr 输出密钥的密文
+    pub fn decrypt(&self, message: &EciesCiphertext) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+        if message.version != ECIES_VERSION {
+            return Err(ecies_error(format!(
+                "Unsupported ciphertext version: {}",
+                message.version
+            )));
+        }
+        if message.kdf != ECIES_KDF {
+            return Err(ecies_error(format!("Unsupported kdf: {}", message.kdf)));
+        }
+        if message.cipher != ECIES_CIPHER {
+            return Err(ecies_error(format!(
+                "Unsupported cipher: {}",
+                message.cipher
+            )));
+        }
+
+        let ephemeral_key = hex::decode(&message.ephemeral_key)?;
+        let recipient_key = hex::decode(&message.recipient)?;
+        let nonce = hex::decode(&message.nonce)?;
+        if nonce.len() != 24 {
+            return Err(ecies_error("Invalid nonce length"));
+        }
+        let ciphertext = hex::decode(&message.ciphertext)?;
+        let ephemeral_public = secp256k1::PublicKey::from_slice(&ephemeral_key)?;
+
+        let mut secret = if recipient_key == self.public_key.inner.serialize() {
+            self.private_key.inner
+        } else {
+            let secret = self.taproot_output_secret();
+            let secp = Secp256k1::signing_only();
+            if recipient_key != secret.public_key(&secp).serialize() {
+                return Err(ecies_error("Message is not addressed to this wallet"));
+            }
+            secret
+        };
+        let shared = ecdh(&secret, &ephemeral_public);
+        secret.non_secure_erase();
+
+        let key = derive_key(&shared, &ephemeral_key, &recipient_key)?;
+        let cipher = XChaCha20Poly1305::new((&*key).into());
+        let aad = [&ephemeral_key[..], &recipient_key[..]].concat();
+        cipher
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map(Zeroizing::new)
+            .map_err(|_| ecies_error("Corrupted ciphertext"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::Network;
+
+    use super::*;
+    use crate::address::{AddressInfo, MnemonicInfo};
+
+    #[test]
+    fn test_ecdh_and_ecies() {
+        let alice = FreeWebMovementAddress::random();
+        let bob = FreeWebMovementAddress::random();
+        assert_eq!(
+            *alice.shared_secret(&bob.public_key),
+            *bob.shared_secret(&alice.public_key)
+        );
+
+        let memo = "pay 1 coin for lunch".as_bytes();
+        let message = encrypt(&bob.public_key, memo).unwrap();
+        let json = message.to_json().unwrap();
+        let loaded = EciesCiphertext::from_json(&json).unwrap();
+        assert_eq!(bob.decrypt(&loaded).unwrap().as_slice(), memo);
+        assert!(alice.decrypt(&loaded).is_err());
+
+        // 篡改密文或版本后无法解密
+        let mut tampered = loaded.clone();
+        let mut bytes = hex::decode(&tampered.ciphertext).unwrap();
+        bytes[0] ^= 1;
+        tampered.ciphertext = hex::encode(bytes);
+        assert!(bob.decrypt(&tampered).is_err());
+        let mut tampered = loaded;
+        tampered.version = ECIES_VERSION + 1;
+        assert!(bob.decrypt(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_to_address() {
+        let mnemonic_info = MnemonicInfo {
+            language: bip39::Language::English,
+            word_count: 12,
+            phrase: String::new().into(),
+            passphrase: String::new().into(),
+        };
+        let taproot = FreeWebMovementAddress::try_new(
+            mnemonic_info,
+            Some(AddressInfo::new(Network::Bitcoin, AddressType::P2tr)),
+        )
+        .unwrap();
+        let address = PublicAddress::from_str(&taproot.to_string()).unwrap();
+        let message = encrypt_to_address(&address, b"memo").unwrap();
+        assert_eq!(taproot.decrypt(&message).unwrap().as_slice(), b"memo");
+
+        let legacy = FreeWebMovementAddress::random();
+        let address = PublicAddress::from_str(&legacy.to_string()).unwrap();
+        assert!(encrypt_to_address(&address, b"memo").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use bip39::{Language, Mnemonic};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::address::{AddressInfo, FreeWebMovementAddress, MnemonicInfo};
+use crate::consts::{DICE_MAX_SIDES, MNEMONIC_WORD_COUNTS};
+use crate::error::AccountError;
+
+/// 用户提供的熵来源
+#[derive(Debug, Clone, Copy)]
+pub enum EntropyInput<'a> {
+    /// 骰子点数（1..=sides）；面数不超过 9 时可连写如 "3615..."，否则用空格或逗号分隔
+    Dice { rolls: &'a str, sides: u32 },
+    /// 硬币正反面：0/1 或 h/t
+    Coins(&'a str),
+    /// 十六进制熵，长度必须与单词数对应
+    Hex(&'a str),
+}
+
+/// 单词数对应的熵字节数：12 词 16 字节 … 24 词 32 字节
+pub fn entropy_bytes(word_count: usize) -> Result<usize, AccountError> {
+    if !MNEMONIC_WORD_COUNTS.contains(&word_count) {
+        return Err(AccountError::Entropy(format!(
+            "word count {} is invalid, expected one of {:?}",
+            word_count, MNEMONIC_WORD_COUNTS
+        )));
+    }
+    Ok(word_count / 3 * 4)
+}
+
+/// 达到 bits 位熵所需的最少骰子次数
+pub fn required_rolls(sides: u32, bits: usize) -> usize {
+    (bits as f64 / (sides as f64).log2()).ceil() as usize
+}
+
+/// 规范化骰子点数，返回参与哈希的字符串与次数
+fn normalize_dice(rolls: &str, sides: u32) -> Result<(Zeroizing<String>, usize), AccountError> {
+    if !(2..=DICE_MAX_SIDES).contains(&sides) {
+        return Err(AccountError::Entropy(format!(
+            "dice must have 2..={} sides",
+            DICE_MAX_SIDES
+        )));
+    }
+    let values: Vec<&str> = if sides <= 9 {
+        rolls
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .flat_map(|part| {
+                part.char_indices()
+                    .map(move |(i, c)| &part[i..i + c.len_utf8()])
+            })
+            .collect()
+    } else {
+        rolls
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|v| !v.is_empty())
+            .collect()
+    };
+    for value in &values {
+        match value.parse::<u32>() {
+            Ok(v) if (1..=sides).contains(&v) => {}
+            _ => {
+                return Err(AccountError::Entropy(format!(
+                    "dice roll \"{}\" is not between 1 and {}",
+                    value, sides
+                )));
+            }
+        }
+    }
+    let separator = if sides <= 9 { "" } else { " " };
+    Ok((Zeroizing::new(values.join(separator)), values.len()))
+}
+
+/// 规范化硬币结果为 0/1 字符串
+fn normalize_coins(flips: &str) -> Result<Zeroizing<String>, AccountError> {
+    let mut bits = Zeroizing::new(String::new());
+    for c in flips.chars().filter(|c| !c.is_whitespace() && *c != ',') {
+        match c.to_ascii_lowercase() {
+            '0' | 't' => bits.push('0'),
+            '1' | 'h' => bits.push('1'),
+            other => {
+                return Err(AccountError::Entropy(format!(
+                    "coin flip '{}' must be 0/1 or h/t",
+                    other
+                )));
+            }
+        }
+    }
+    Ok(bits)
+}
+
+/// 收集助记词熵：骰子和硬币取规范化字符串的 SHA-256 截断（便于离线复核），十六进制原样使用；
+/// mix_os 为 true 时再与系统随机数异或，任一来源足够随机即可保证安全
+pub fn collect_entropy(
+    input: EntropyInput,
+    word_count: usize,
+    mix_os: bool,
+) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    let bytes = entropy_bytes(word_count)?;
+    let bits = bytes * 8;
+    let mut entropy = match input {
+        EntropyInput::Dice { rolls, sides } => {
+            let (normalized, count) = normalize_dice(rolls, sides)?;
+            let required = required_rolls(sides, bits);
+            if count < required {
+                return Err(AccountError::Entropy(format!(
+                    "{} rolls of a {}-sided die give less than {} bits, at least {} rolls are required",
+                    count, sides, bits, required
+                )));
+            }
+            Zeroizing::new(Sha256::digest(normalized.as_bytes())[..bytes].to_vec())
+        }
+        EntropyInput::Coins(flips) => {
+            let normalized = normalize_coins(flips)?;
+            if normalized.len() < bits {
+                return Err(AccountError::Entropy(format!(
+                    "{} coin flips give less than {} bits",
+                    normalized.len(),
+                    bits
+                )));
+            }
+            Zeroizing::new(Sha256::digest(normalized.as_bytes())[..bytes].to_vec())
+        }
+        EntropyInput::Hex(hex_str) => {
+            let entropy = Zeroizing::new(hex::decode(hex_str.trim())?);
+            if entropy.len() != bytes {
+                return Err(AccountError::Entropy(format!(
+                    "{} words need {} hex characters, got {}",
+                    word_count,
+                    bytes * 2,
+                    hex_str.trim().len()
+                )));
+            }
+            entropy
+        }
+    };
+    if mix_os {
+        let mut random = Zeroizing::new(vec![0u8; bytes]);
+        OsRng.fill_bytes(&mut random);
+        for (e, r) in entropy.iter_mut().zip(random.iter()) {
+            *e ^= r;
+        }
+    }
+    Ok(entropy)
+}
+
+impl FreeWebMovementAddress {
+    /// 由原始熵创建地址，单词数由熵长度决定
+    pub fn from_entropy(
+        entropy: &[u8],
+        language: Language,
+        passphrase: &str,
+        address_info: Option<AddressInfo>,
+    ) -> Result<Self, AccountError> {
+        let mnemonic = Mnemonic::from_entropy_in(language, entropy)?;
+        let mnemonic_info = MnemonicInfo {
+            language,
+            word_count: mnemonic.word_count(),
+            phrase: mnemonic.to_string().into(),
+            passphrase: passphrase.to_string().into(),
+        };
+        FreeWebMovementAddress::try_new(mnemonic_info, address_info)
+    }
+
+    /// 导出助记词的原始熵，供离线审计
+    pub fn to_entropy(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.mnemonic.to_entropy())
+    }
+
+    pub fn entropy_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(&*self.to_entropy()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_entropy_round_trip() {
+        // BIP39 官方向量
+        let hex_str = "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f";
+        let entropy = collect_entropy(EntropyInput::Hex(hex_str), 12, false).unwrap();
+        let address =
+            FreeWebMovementAddress::from_entropy(&entropy, Language::English, "", None).unwrap();
+        assert_eq!(
+            address.mnemonic.to_string(),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+        );
+        assert_eq!(*address.entropy_hex(), hex_str);
+
+        assert!(collect_entropy(EntropyInput::Hex(hex_str), 24, false).is_err());
+        assert!(collect_entropy(EntropyInput::Hex("zz"), 12, false).is_err());
+
+        // 混入系统随机数后熵改变，长度不变
+        let mixed = collect_entropy(EntropyInput::Hex(hex_str), 12, true).unwrap();
+        assert_eq!(mixed.len(), 16);
+        assert_ne!(*mixed, *entropy);
+    }
+
+    #[test]
+    fn test_dice_and_coins() {
+        assert_eq!(required_rolls(6, 128), 50);
+        assert_eq!(required_rolls(6, 256), 100);
+        assert_eq!(required_rolls(2, 256), 256);
+
+        // 骰子结果为规范化字符串的 SHA-256，可离线复核
+        let rolls = "123456".repeat(17);
+        let entropy = collect_entropy(
+            EntropyInput::Dice {
+                rolls: &rolls,
+                sides: 6,
+            },
+            24,
+            false,
+        )
+        .unwrap();
+        assert_eq!(*entropy, Sha256::digest(rolls.as_bytes()).to_vec());
+
+        // 空格与逗号不影响结果
+        let spaced = rolls.chars().map(|c| format!("{} ", c)).collect::<String>();
+        let same = collect_entropy(
+            EntropyInput::Dice {
+                rolls: &spaced,
+                sides: 6,
+            },
+            24,
+            false,
+        )
+        .unwrap();
+        assert_eq!(*same, *entropy);
+
+        let short = EntropyInput::Dice {
+            rolls: "1234",
+            sides: 6,
+        };
+        assert!(collect_entropy(short, 12, false).is_err());
+        let invalid = EntropyInput::Dice {
+            rolls: &"7".repeat(50),
+            sides: 6,
+        };
+        assert!(collect_entropy(invalid, 12, false).is_err());
+        let d20 = "20,".repeat(30);
+        let d20 = EntropyInput::Dice {
+            rolls: &d20,
+            sides: 20,
+        };
+        assert!(collect_entropy(d20, 12, false).is_ok());
+
+        let coins = "HT".repeat(64);
+        let entropy = collect_entropy(EntropyInput::Coins(&coins), 12, false).unwrap();
+        assert_eq!(*entropy, Sha256::digest("10".repeat(64).as_bytes())[..16]);
+        assert!(collect_entropy(EntropyInput::Coins("htx"), 12, false).is_err());
+        assert!(collect_entropy(EntropyInput::Coins(&coins), 24, false).is_err());
+        assert!(entropy_bytes(13).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use std::{fmt, io};
+
+use crate::consts::COIN_PREFIX;
+
+/// 本库统一的错误类型
+#[derive(Debug)]
+pub enum AccountError {
+    /// 助记词生成或解析失败
+    Mnemonic(bip39::Error),
+    /// BIP32 派生失败（路径格式错误、派生出错等）
+    Derivation(bitcoin::bip32::Error),
+    /// 公钥 / 私钥字节无效
+    Key(bitcoin::key::Error),
+    /// 签名字节无效
+    Signature(secp256k1::Error),
+    /// 地址构造失败
+    Address(bitcoin::address::Error),
+    /// 地址字符串解析失败（格式或校验和错误）
+    AddressParse(bitcoin::address::ParseError),
+    /// 地址字符串的币种前缀不正确
+    InvalidPrefix(String),
+    /// 不支持的助记词语言名称
+    UnsupportedLanguage(String),
+    /// passphrase 无法派生出钱包中的地址
+    PassphraseMismatch,
+    /// 不支持的地址类型
+    UnsupportedAddressType(String),
+    /// 网络与 xpub 不一致（如主网 xpub 用于测试网）
+    NetworkMismatch(String),
+    /// 多签参数无效（门限、公钥数量、重复公钥等）
+    Multisig(String),
+    /// 十六进制字符串无效
+    Hex(hex::FromHexError),
+    /// Base64 字符串无效
+    Base64(base64::DecodeError),
+    /// JSON 序列化 / 反序列化失败
+    Serde(serde_json::Error),
+    /// 文件读写失败
+    Io(io::Error),
+    /// 加密钱包相关错误（口令错误、格式不支持等）
+    Keystore(String),
+    /// BIP85 参数无效（长度超出范围等）
+    Bip85(String),
+    /// SLIP-39 分片无效（校验和错误、参数不一致、分片不足等）
+    Slip39(String),
+    /// 靓号模式无效或不可能匹配
+    Vanity(String),
+    /// 用户提供的熵无效或位数不足
+    Entropy(String),
+    /// ECIES 加解密失败（密文损坏、收件人不匹配、地址不含公钥等）
+    Ecies(String),
+    /// 钱包账户操作无效（标签重复、账户不存在、删除最后一个账户等）
+    WalletAccount(String),
+    /// 钱包文件版本不受支持（来自更新的版本或版本字段无效）
+    WalletSchema(String),
+    /// 钱包文件或目录权限过宽（严格模式下拒绝读取）
+    Permissions(String),
+    /// 钱包正被其他进程使用，等待锁超时
+    WalletLocked(String),
+    /// JNI 调用失败或句柄无效
+    Jni(String),
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccountError::Mnemonic(e) => write!(f, "Invalid mnemonic: {}", e),
+            AccountError::Derivation(e) => write!(f, "Key derivation failed: {}", e),
+            AccountError::Key(e) => write!(f, "Invalid key: {}", e),
+            AccountError::Signature(e) => write!(f, "Invalid signature: {}", e),
+            AccountError::Address(e) => write!(f, "Invalid address: {}", e),
+            AccountError::AddressParse(e) => write!(f, "Invalid address: {}", e),
+            AccountError::InvalidPrefix(s) => {
+                write!(f, "Invalid address prefix, expected {}: {}", COIN_PREFIX, s)
+            }
+            AccountError::UnsupportedLanguage(s) => write!(f, "Unsupported language: {}", s),
+            AccountError::PassphraseMismatch => {
+                write!(f, "Passphrase does not match the wallet address")
+            }
+            AccountError::UnsupportedAddressType(t) => {
+                write!(f, "Unsupported address type: {}", t)
+            }
+            AccountError::NetworkMismatch(msg) => write!(f, "Network mismatch: {}", msg),
+            AccountError::Multisig(msg) => write!(f, "Invalid multisig: {}", msg),
+            AccountError::Hex(e) => write!(f, "Invalid hex: {}", e),
+            AccountError::Base64(e) => write!(f, "Invalid base64: {}", e),
+            AccountError::Serde(e) => write!(f, "Serialization failed: {}", e),
+            AccountError::Io(e) => write!(f, "IO error: {}", e),
+            AccountError::Keystore(msg) => write!(f, "Keystore error: {}", msg),
+            AccountError::Bip85(msg) => write!(f, "Invalid BIP85 request: {}", msg),
+            AccountError::Slip39(msg) => write!(f, "Invalid SLIP-39 shares: {}", msg),
+            AccountError::Vanity(msg) => write!(f, "Invalid vanity pattern: {}", msg),
+            AccountError::Entropy(msg) => write!(f, "Invalid entropy: {}", msg),
+            AccountError::Ecies(msg) => write!(f, "ECIES error: {}", msg),
+            AccountError::WalletAccount(msg) => write!(f, "Invalid wallet account: {}", msg),
+            AccountError::WalletSchema(msg) => write!(f, "Unsupported wallet file: {}", msg),
+            AccountError::Permissions(msg) => write!(f, "Insecure permissions: {}", msg),
+            AccountError::WalletLocked(msg) => write!(f, "Wallet is locked: {}", msg),
+            AccountError::Jni(msg) => write!(f, "JNI error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AccountError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AccountError::Mnemonic(e) => Some(e),
+            AccountError::Derivation(e) => Some(e),
+            AccountError::Key(e) => Some(e),
+            AccountError::Signature(e) => Some(e),
+            AccountError::Address(e) => Some(e),
+            AccountError::AddressParse(e) => Some(e),
+            AccountError::Hex(e) => Some(e),
+            AccountError::Base64(e) => Some(e),
+            AccountError::Serde(e) => Some(e),
+            AccountError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// 供仍返回 io::Result 的接口使用：Io 错误原样返回，其他错误包装为 io::Error
+impl From<AccountError> for io::Error {
+    fn from(e: AccountError) -> Self {
+        match e {
+            AccountError::Io(e) => e,
+            other => io::Error::other(other),
+        }
+    }
+}
+
+impl From<bip39::Error> for AccountError {
+    fn from(e: bip39::Error) -> Self {
+        AccountError::Mnemonic(e)
+    }
+}
+
+impl From<bitcoin::bip32::Error> for AccountError {
+    fn from(e: bitcoin::bip32::Error) -> Self {
+        AccountError::Derivation(e)
+    }
+}
+
+impl From<bitcoin::key::Error> for AccountError {
+    fn from(e: bitcoin::key::Error) -> Self {
+        AccountError::Key(e)
+    }
+}
+
+impl From<secp256k1::Error> for AccountError {
+    fn from(e: secp256k1::Error) -> Self {
+        AccountError::Signature(e)
+    }
+}
+
+impl From<bitcoin::address::Error> for AccountError {
+    fn from(e: bitcoin::address::Error) -> Self {
+        AccountError::Address(e)
+    }
+}
+
+impl From<bitcoin::address::ParseError> for AccountError {
+    fn from(e: bitcoin::address::ParseError) -> Self {
+        AccountError::AddressParse(e)
+    }
+}
+
+impl From<hex::FromHexError> for AccountError {
+    fn from(e: hex::FromHexError) -> Self {
+        AccountError::Hex(e)
+    }
+}
+
+impl From<base64::DecodeError> for AccountError {
+    fn from(e: base64::DecodeError) -> Self {
+        AccountError::Base64(e)
+    }
+}
+
+impl From<serde_json::Error> for AccountError {
+    fn from(e: serde_json::Error) -> Self {
+        AccountError::Serde(e)
+    }
+}
+
+impl From<io::Error> for AccountError {
+    fn from(e: io::Error) -> Self {
+        AccountError::Io(e)
+    }
+}
+
+impl From<jni::errors::Error> for AccountError {
+    fn from(e: jni::errors::Error) -> Self {
+        AccountError::Jni(e.to_string())
+    }
+}
@@ -1,121 +1,249 @@
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use crate::account::{AccountIndexes, Chain, HdAccount};
 use crate::address::FreeWebMovementAddress;
+use crate::backup::{BackupRetention, backup_file_path, list_backups, prune_backups};
+use crate::consts::{
+    BACKUP_PREFIX, DEFAULT_ACCOUNT_LABEL, DEFAULT_BACKUP_KEEP_DAILY, DEFAULT_BACKUP_KEEP_LAST,
+    DEFAULT_BACKUP_KEEP_WEEKLY, LEGACY_JNI_BACKUP_PREFIX, WALLET_LOCK_TIMEOUT_MS,
+};
+use crate::error::AccountError;
+use crate::mnemonic::{parse_language, validate_mnemonic};
+use crate::storage::{
+    LockMode, PermissionPolicy, WalletLock, check_permissions, create_private_dir,
+};
+use crate::wallet::{
+    WalletAccount, WalletFile, decode_wallet, encode_wallet, read_wallet_file, write_wallet_file,
+};
 use chrono::Local;
 use jni::JNIEnv;
 use jni::objects::{JClass, JString};
-use jni::sys::jlong;
+use jni::sys::{jint, jlong};
+use zeroize::Zeroizing;
+
+const EXCEPTION_CLASS: &str = "java/lang/RuntimeException";
 
 #[inline(always)]
-fn get_address_mut(ptr: jlong) -> &'static mut FreeWebMovementAddress {
+fn get_address_mut(ptr: jlong) -> Result<&'static mut FreeWebMovementAddress, AccountError> {
     // 将 jlong 指针安全转换为 Rust 可用的可变引用
-    unsafe { &mut *(ptr as *mut FreeWebMovementAddress) }
+    if ptr == 0 {
+        return Err(AccountError::Jni("Null address handle".to_string()));
+    }
+    Ok(unsafe { &mut *(ptr as *mut FreeWebMovementAddress) })
+}
+
+/// 出错时向 Java 抛出 RuntimeException，并返回默认值（0 或 null）
+fn or_throw<T: Default>(env: &mut JNIEnv, result: Result<T, AccountError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            tracing::error!("jni call failed: {}", e);
+            let _ = env.throw_new(EXCEPTION_CLASS, e.to_string());
+            T::default()
+        }
+    }
+}
+
+fn get_string(env: &mut JNIEnv, path: &JString) -> Result<String, AccountError> {
+    Ok(env.get_string(path)?.into())
+}
+
+fn data_dir() -> Result<PathBuf, AccountError> {
+    dirs::data_dir().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "无法获取 app_data").into()
+    })
+}
+
+fn default_wallet_path(path_str: &str) -> Result<PathBuf, AccountError> {
+    if path_str.is_empty() {
+        // 默认路径使用 app_data/zz_wallet.json
+        let mut dir = data_dir()?;
+        dir.push("zz_wallet.json");
+        Ok(dir)
+    } else {
+        Ok(PathBuf::from(path_str))
+    }
 }
 
+fn lock_timeout() -> Duration {
+    Duration::from_millis(WALLET_LOCK_TIMEOUT_MS)
+}
+
+/// 以带版本号的钱包格式写入单个地址
+fn write_json(pathbuf: &Path, address: &FreeWebMovementAddress) -> Result<(), AccountError> {
+    if let Some(parent) = pathbuf.parent()
+        && !parent.exists()
+    {
+        create_private_dir(parent)?;
+    }
+
+    let data = WalletFile::single(WalletAccount::new(
+        DEFAULT_ACCOUNT_LABEL,
+        address.clone(),
+        AccountIndexes::default(),
+    ));
+    write_wallet_file(pathbuf, &encode_wallet(&data, None)?)
+}
+
+/// Java 侧无法配置保留策略，默认备份按固定策略清理
+fn default_retention() -> BackupRetention {
+    BackupRetention {
+        keep_last: DEFAULT_BACKUP_KEEP_LAST,
+        keep_daily: DEFAULT_BACKUP_KEEP_DAILY,
+        keep_weekly: DEFAULT_BACKUP_KEEP_WEEKLY,
+    }
+}
+
+/// 在 dir 中写入与 Wallet::backup 同名规则的带时间戳备份，并按默认策略清理旧备份
+fn backup_to_dir(dir: &Path, address: &FreeWebMovementAddress) -> Result<PathBuf, AccountError> {
+    let pathbuf = backup_file_path(dir, BACKUP_PREFIX, Local::now().naive_local())?;
+    {
+        let _lock = WalletLock::acquire(&pathbuf, LockMode::Exclusive, lock_timeout())?;
+        write_json(&pathbuf, address)?;
+    }
+    prune_backups(dir, BACKUP_PREFIX, &default_retention())?;
+    Ok(pathbuf)
+}
+
+/// dir 中最新的备份；没有新格式备份时退回旧版的 backup_*.json
+fn latest_backup(dir: &Path) -> Result<PathBuf, AccountError> {
+    if let Some(backup) = list_backups(dir, BACKUP_PREFIX)?.pop() {
+        return Ok(backup.path);
+    }
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let legacy = path
+                .file_name()?
+                .to_string_lossy()
+                .starts_with(LEGACY_JNI_BACKUP_PREFIX);
+            (path.is_file() && legacy).then_some(path)
+        })
+        .collect();
+    backups.sort();
+    backups.pop().ok_or_else(|| {
+        AccountError::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "没有找到备份文件",
+        ))
+    })
+}
+
+/// # Safety
+///
+/// 由 Java 侧调用，返回的句柄必须通过 `destroy` 释放。
 #[unsafe(no_mangle)]
-pub unsafe extern "system" fn Java_rs_zz_coin_Address_create(_env: JNIEnv, _: JClass) -> jlong {
-    let addr = FreeWebMovementAddress::random();
-    Box::into_raw(Box::new(addr)) as jlong
+pub unsafe extern "system" fn Java_rs_zz_coin_Address_create(mut env: JNIEnv, _: JClass) -> jlong {
+    let result =
+        FreeWebMovementAddress::try_random().map(|addr| Box::into_raw(Box::new(addr)) as jlong);
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是 `create` / `fromJSON` 返回且尚未释放的句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_destroy(_env: JNIEnv, _: JClass, ptr: jlong) {
-    let _ = unsafe { Box::from_raw(ptr as *mut FreeWebMovementAddress) };
+    if ptr != 0 {
+        let _ = unsafe { Box::from_raw(ptr as *mut FreeWebMovementAddress) };
+    }
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_prefix<'a>(
-    env: JNIEnv<'a>,
+    mut env: JNIEnv<'a>,
     _: JClass<'a>,
     ptr: jlong,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let prefix = env
-        .new_string(&address.info.prefix)
-        .expect("Couldn't create Java string");
-    prefix
+    let result = get_address_mut(ptr).and_then(|address| Ok(env.new_string(&address.info.prefix)?));
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_toString<'a>(
-    env: JNIEnv<'a>,
+    mut env: JNIEnv<'a>,
     _: JClass<'a>,
     ptr: jlong,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let str = env
-        .new_string(&address.to_string())
-        .expect("Couldn't create Java string");
-    str
+    let result = get_address_mut(ptr).and_then(|address| Ok(env.new_string(address.to_string())?));
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_privateKey<'a>(
-    env: JNIEnv<'a>,
+    mut env: JNIEnv<'a>,
     _: JClass<'a>,
     ptr: jlong,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let hex_string = hex::encode(&&address.private_key.to_bytes());
-
-    let str = env
-        .new_string(hex_string.to_string())
-        .expect("Couldn't create Java string");
-    str
+    let result = get_address_mut(ptr).and_then(|address| {
+        let hex_string = hex::encode(address.private_key.to_bytes());
+        Ok(env.new_string(hex_string)?)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_publicKey<'a>(
-    env: JNIEnv<'a>,
+    mut env: JNIEnv<'a>,
     _: JClass<'a>,
     ptr: jlong,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let hex_string = hex::encode(&&address.public_key.to_bytes());
-
-    let str = env
-        .new_string(hex_string.to_string())
-        .expect("Couldn't create Java string");
-    str
+    let result = get_address_mut(ptr).and_then(|address| {
+        let hex_string = hex::encode(address.public_key.to_bytes());
+        Ok(env.new_string(hex_string)?)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_toJSON<'a>(
-    env: JNIEnv<'a>,
+    mut env: JNIEnv<'a>,
     _: JClass<'a>,
     ptr: jlong,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-    let json = serde_json::to_string_pretty(address)
-        .expect("Failed to serialize FreeWebMovementAddress to JSON");
-
-    let str = env
-        .new_string(json.to_string())
-        .expect("Couldn't create Java string");
-    str
+    let result = get_address_mut(ptr).and_then(|address| {
+        let json = serde_json::to_string_pretty(address)?;
+        Ok(env.new_string(json)?)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// 返回的句柄必须通过 `destroy` 释放；解析失败时抛出异常并返回 0。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_fromJSON<'a>(
     mut env: JNIEnv<'a>,
     _: JClass<'a>,
     json: JString,
 ) -> jlong {
-    let str = env
-        .get_string(&json)
-        .expect("Couldn't get string from JString");
-    let str = str.to_str().expect("Couldn't convert JString to str");
-    let addr = FreeWebMovementAddress::from_json(&str)
-        .expect("Failed to deserialize FreeWebMovementAddress from JSON");
-    Box::into_raw(Box::new(addr)) as jlong
+    let result = get_string(&mut env, &json).and_then(|str| {
+        let addr = FreeWebMovementAddress::try_from_json(&str)?;
+        Ok(Box::into_raw(Box::new(addr)) as jlong)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_save<'a>(
     mut env: JNIEnv<'a>,
@@ -123,33 +251,19 @@ pub unsafe extern "system" fn Java_rs_zz_coin_Address_save<'a>(
     ptr: jlong,
     path: JString<'a>,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let binding = env.get_string(&path).expect("无法获取路径");
-    let path_str = binding.to_str().expect("Invalid UTF-8 in path");
-    let pathbuf = if path_str.is_empty() {
-        // 默认路径使用 app_data/zz_wallet.json
-        let mut dir = dirs::data_dir().expect("无法获取 app_data");
-        dir.push("zz_wallet.json");
-        dir
-    } else {
-        PathBuf::from(path_str)
-    };
-
-    if let Some(parent) = pathbuf.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).expect("无法创建保存目录");
-        }
-    }
-
-    let json = serde_json::to_string_pretty(address).expect("序列化失败");
-    let mut file = fs::File::create(&pathbuf).expect("无法创建钱包文件");
-    file.write_all(json.as_bytes()).expect("写入失败");
-
-    env.new_string(pathbuf.to_string_lossy().to_string())
-        .expect("无法创建返回字符串")
+    let result = get_address_mut(ptr).and_then(|address| {
+        let path_str = get_string(&mut env, &path)?;
+        let pathbuf = default_wallet_path(&path_str)?;
+        let _lock = WalletLock::acquire(&pathbuf, LockMode::Exclusive, lock_timeout())?;
+        write_json(&pathbuf, address)?;
+        Ok(env.new_string(pathbuf.to_string_lossy())?)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_load<'a>(
     mut env: JNIEnv<'a>,
@@ -157,26 +271,19 @@ pub unsafe extern "system" fn Java_rs_zz_coin_Address_load<'a>(
     ptr: jlong,
     path: JString<'a>,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let binding = env.get_string(&path).expect("无法获取路径");
-    let path_str = binding.to_str().expect("Invalid UTF-8 in path");
-    let pathbuf = if path_str.is_empty() {
-        // 默认路径使用 app_data/zz_wallet.json
-        let mut dir = dirs::data_dir().expect("无法获取 app_data");
-        dir.push("zz_wallet.json");
-        dir
-    } else {
-        PathBuf::from(path_str)
-    };
-
-    let json = fs::read_to_string(&pathbuf).expect("读取文件失败");
-    *address = serde_json::from_str(&json).expect("反序列化失败");
-
-    env.new_string(pathbuf.to_string_lossy().to_string())
-        .expect("无法创建返回字符串")
+    let result = get_address_mut(ptr).and_then(|address| {
+        let path_str = get_string(&mut env, &path)?;
+        let pathbuf = default_wallet_path(&path_str)?;
+        *address = read_wallet_file(&pathbuf, None, PermissionPolicy::default(), lock_timeout())?
+            .into_default_address();
+        Ok(env.new_string(pathbuf.to_string_lossy())?)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_backup<'a>(
     mut env: JNIEnv<'a>,
@@ -184,34 +291,26 @@ pub unsafe extern "system" fn Java_rs_zz_coin_Address_backup<'a>(
     ptr: jlong,
     path: JString<'a>,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let binding = env.get_string(&path).expect("无法获取路径");
-    let path_str = binding.to_str().expect("Invalid UTF-8 in path");
-    let pathbuf = if path_str.is_empty() {
-        // 默认路径 app_data/backup_YYYYMMDD_HHMMSS.json
-        let mut dir = dirs::data_dir().expect("无法获取 app_data");
-        let ts = Local::now().format("%Y%m%d_%H%M%S");
-        dir.push(format!("backup_{}.json", ts));
-        dir
-    } else {
-        PathBuf::from(path_str)
-    };
-
-    if let Some(parent) = pathbuf.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).expect("无法创建备份目录");
-        }
-    }
-
-    let json = serde_json::to_string_pretty(address).expect("序列化失败");
-    let mut file = fs::File::create(&pathbuf).expect("无法创建备份文件");
-    file.write_all(json.as_bytes()).expect("写入失败");
-
-    env.new_string(pathbuf.to_string_lossy().to_string())
-        .expect("无法创建返回字符串")
+    let result = get_address_mut(ptr).and_then(|address| {
+        let path_str = get_string(&mut env, &path)?;
+        let pathbuf = if path_str.is_empty() {
+            // 默认路径 app_data/wallet_backup_YYYYMMDDHHMMSS.json，并清理过期备份
+            backup_to_dir(&data_dir()?, address)?
+        } else {
+            // 调用方指定的文件不参与清理
+            let pathbuf = PathBuf::from(path_str);
+            let _lock = WalletLock::acquire(&pathbuf, LockMode::Exclusive, lock_timeout())?;
+            write_json(&pathbuf, address)?;
+            pathbuf
+        };
+        Ok(env.new_string(pathbuf.to_string_lossy())?)
+    });
+    or_throw(&mut env, result)
 }
 
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。
 #[unsafe(no_mangle)]
 pub unsafe extern "system" fn Java_rs_zz_coin_Address_recovery<'a>(
     mut env: JNIEnv<'a>,
@@ -219,37 +318,73 @@ pub unsafe extern "system" fn Java_rs_zz_coin_Address_recovery<'a>(
     ptr: jlong,
     path: JString<'a>,
 ) -> JString<'a> {
-    let address = get_address_mut(ptr);
-
-    let binding = env.get_string(&path).expect("无法获取路径");
-    let path_str = binding.to_str().expect("Invalid UTF-8 in path");
-    let pathbuf = if path_str.is_empty() {
-        // 默认恢复使用 app_data 下最新的 backup_*.json
-        let mut dir = dirs::data_dir().expect("无法获取 app_data");
-        dir.push(""); // data_dir 本身就是目录
-        let mut backups: Vec<PathBuf> = fs::read_dir(&dir)
-            .expect("Failed to read backup directory")
-            .filter_map(|entry| {
-                let entry = entry.ok()?;
-                let path = entry.path();
-                if path.is_file() && path.file_name()?.to_string_lossy().starts_with("backup_") {
-                    Some(path)
-                } else {
-                    None
-                }
-            })
-            .collect();
-        backups.sort();
-        backups.pop().expect("没有找到备份文件")
-    } else {
-        PathBuf::from(path_str)
-    };
+    let result = get_address_mut(ptr).and_then(|address| {
+        let path_str = get_string(&mut env, &path)?;
+        let pathbuf = if path_str.is_empty() {
+            // 默认恢复使用 app_data 下最新的备份
+            latest_backup(&data_dir()?)?
+        } else {
+            PathBuf::from(path_str)
+        };
+
+        // 与 Wallet::recovery 一致，权限过宽时记录警告
+        check_permissions(&pathbuf, PermissionPolicy::default())?;
+        let json = {
+            let _lock = WalletLock::acquire(&pathbuf, LockMode::Shared, lock_timeout())?;
+            fs::read_to_string(&pathbuf)?
+        };
+        *address = decode_wallet(&json, None)?.into_default_address();
+        Ok(env.new_string(pathbuf.to_string_lossy())?)
+    });
+    or_throw(&mut env, result)
+}
 
-    let json = fs::read_to_string(&pathbuf).expect("读取备份文件失败");
-    *address = serde_json::from_str(&json).expect("反序列化失败");
+/// # Safety
+///
+/// `ptr` 必须是有效的地址句柄。chain 为 0（收款）或 1（找零），passphrase 为创建地址时的 BIP39 passphrase；
+/// 返回派生地址的字符串，passphrase 不匹配时抛出异常。
+#[unsafe(no_mangle)]
+pub unsafe extern "system" fn Java_rs_zz_coin_Address_derive<'a>(
+    mut env: JNIEnv<'a>,
+    _: JClass<'a>,
+    ptr: jlong,
+    chain: jint,
+    index: jint,
+    passphrase: JString<'a>,
+) -> JString<'a> {
+    let result = get_address_mut(ptr).and_then(|address| {
+        let passphrase = Zeroizing::new(get_string(&mut env, &passphrase)?);
+        let chain = match chain {
+            0 => Chain::Receive,
+            1 => Chain::Change,
+            other => return Err(AccountError::Jni(format!("Invalid chain: {}", other))),
+        };
+        let index = u32::try_from(index)
+            .map_err(|_| AccountError::Jni(format!("Invalid index: {}", index)))?;
+        let account = HdAccount::from_address(address, &passphrase, AccountIndexes::default())?;
+        let derived = account.derive(chain, index)?;
+        Ok(env.new_string(derived.to_string())?)
+    });
+    or_throw(&mut env, result)
+}
 
-    env.new_string(pathbuf.to_string_lossy().to_string())
-        .expect("无法创建返回字符串")
+/// # Safety
+///
+/// 由 Java 侧调用；返回检查结果的 JSON，语言名称无效时抛出异常并返回 null。
+#[unsafe(no_mangle)]
+pub unsafe extern "system" fn Java_rs_zz_coin_Address_validateMnemonic<'a>(
+    mut env: JNIEnv<'a>,
+    _: JClass<'a>,
+    phrase: JString<'a>,
+    language: JString<'a>,
+) -> JString<'a> {
+    let result = get_string(&mut env, &phrase).and_then(|phrase| {
+        let phrase = Zeroizing::new(phrase);
+        let language = parse_language(&get_string(&mut env, &language)?)?;
+        let report = validate_mnemonic(&phrase, language);
+        Ok(env.new_string(serde_json::to_string(&report)?)?)
+    });
+    or_throw(&mut env, result)
 }
 
 #[test]
@@ -342,6 +477,21 @@ fn test_create_address() {
 
     assert!(address_ptr_2 != 0);
 
+    // 非法 JSON 抛出异常并返回 0，而不是 panic
+    let invalid_ptr = unsafe {
+        let class = JClass::default();
+        let env = jvm.attach_current_thread_permanently().unwrap();
+        let java_string: JString = env.new_string("{").expect("Couldn't create Java string!");
+
+        Java_rs_zz_coin_Address_fromJSON(env, class, java_string)
+    };
+    assert_eq!(invalid_ptr, 0);
+    {
+        let env = jvm.attach_current_thread_permanently().unwrap();
+        assert!(env.exception_check().unwrap());
+        env.exception_clear().unwrap();
+    }
+
     let private_key_str = unsafe {
         let class = JClass::default();
         let env = jvm.attach_current_thread_permanently().unwrap();
@@ -381,7 +531,7 @@ fn test_create_address() {
 
         let env = jvm.attach_current_thread_permanently().unwrap();
         let java_path = env.new_string(&save_path_str).unwrap();
-        Java_rs_zz_coin_Address_save(env, class, address_ptr, java_path)
+        Java_rs_zz_coin_Address_save(env, class, address_ptr_2, java_path)
     };
 
     let saved_path = {
@@ -396,7 +546,7 @@ fn test_create_address() {
 
         let env = jvm.attach_current_thread_permanently().unwrap();
         let java_path = env.new_string(&save_path_str).unwrap();
-        Java_rs_zz_coin_Address_load(env, class, address_ptr, java_path);
+        Java_rs_zz_coin_Address_load(env, class, address_ptr_2, java_path);
     }
 
     // backup 测试（使用默认 app_data）
@@ -405,12 +555,19 @@ fn test_create_address() {
 
         let env = jvm.attach_current_thread_permanently().unwrap();
         let java_path = env.new_string("").unwrap(); // 空字符串触发默认路径
-        let jstr = Java_rs_zz_coin_Address_backup(env, class, address_ptr, java_path);
+        let jstr = Java_rs_zz_coin_Address_backup(env, class, address_ptr_2, java_path);
 
         let mut env = jvm.attach_current_thread_permanently().unwrap();
         env.get_string(&jstr).unwrap().to_str().unwrap().to_string()
     };
     assert!(PathBuf::from(&backup_path).exists());
+    assert!(
+        PathBuf::from(&backup_path)
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with(BACKUP_PREFIX)
+    );
     println!("Backup file: {}", backup_path);
 
     // recovery 测试
@@ -419,13 +576,29 @@ fn test_create_address() {
 
         let env = jvm.attach_current_thread_permanently().unwrap();
         let java_path = env.new_string(&backup_path).unwrap();
-        let jstr = Java_rs_zz_coin_Address_recovery(env, class, address_ptr, java_path);
+        let jstr = Java_rs_zz_coin_Address_recovery(env, class, address_ptr_2, java_path);
 
         let mut env = jvm.attach_current_thread_permanently().unwrap();
         env.get_string(&jstr).unwrap().to_str().unwrap().to_string()
     };
     assert_eq!(recovered_path, backup_path);
 
+    // 助记词检查
+    let report = unsafe {
+        let class = JClass::default();
+
+        let env = jvm.attach_current_thread_permanently().unwrap();
+        let phrase = env.new_string("legal winner thank yaer").unwrap();
+        let language = env.new_string("english").unwrap();
+        let jstr = Java_rs_zz_coin_Address_validateMnemonic(env, class, phrase, language);
+
+        let mut env = jvm.attach_current_thread_permanently().unwrap();
+        env.get_string(&jstr).unwrap().to_str().unwrap().to_string()
+    };
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    assert_eq!(report["invalid_word_count"], true);
+    assert_eq!(report["unknown_words"][0]["index"], 3);
+
     // 清理
     let _ = fs::remove_dir_all(&tmp_dir);
     let _ = fs::remove_file(&backup_path);
@@ -437,3 +610,32 @@ fn test_create_address() {
         Java_rs_zz_coin_Address_destroy(env, class, address_ptr_2);
     }
 }
+
+#[test]
+fn test_backup_retention() {
+    let tmp_dir = PathBuf::from("/tmp/test_jni_backup_retention");
+    let _ = fs::remove_dir_all(&tmp_dir);
+    create_private_dir(&tmp_dir).unwrap();
+
+    // 只有旧版备份时仍能找到
+    assert!(latest_backup(&tmp_dir).is_err());
+    let legacy = tmp_dir.join("backup_20240101_000000.json");
+    let address = FreeWebMovementAddress::random();
+    write_json(&legacy, &address).unwrap();
+    assert_eq!(latest_backup(&tmp_dir).unwrap(), legacy);
+
+    let backups: Vec<PathBuf> = (0..DEFAULT_BACKUP_KEEP_LAST + 2)
+        .map(|_| backup_to_dir(&tmp_dir, &address).unwrap())
+        .collect();
+    let kept: Vec<PathBuf> = list_backups(&tmp_dir, BACKUP_PREFIX)
+        .unwrap()
+        .into_iter()
+        .map(|b| b.path)
+        .collect();
+    assert_eq!(kept, backups[2..]);
+    assert_eq!(&latest_backup(&tmp_dir).unwrap(), backups.last().unwrap());
+    // 旧版备份不在清理范围内
+    assert!(legacy.exists());
+
+    let _ = fs::remove_dir_all(&tmp_dir);
+}
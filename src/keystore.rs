@@ -0,0 +1,253 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::consts::{
+    KEYSTORE_CIPHER, KEYSTORE_KDF, KEYSTORE_KDF_M_COST, KEYSTORE_KDF_MAX_M_COST,
+    KEYSTORE_KDF_MAX_P_COST, KEYSTORE_KDF_MAX_T_COST, KEYSTORE_KDF_P_COST, KEYSTORE_KDF_T_COST,
+    KEYSTORE_KEY_SIZE, KEYSTORE_SALT_SIZE, KEYSTORE_VERSION,
+};
+use crate::error::AccountError;
+
+/// Argon2id 参数，随文件一起保存，便于以后调整强度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams {
+            m_cost: KEYSTORE_KDF_M_COST,
+            t_cost: KEYSTORE_KDF_T_COST,
+            p_cost: KEYSTORE_KDF_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    /// 参数来自文件，派生密钥前限制上限，避免构造的文件在校验 MAC 之前耗尽内存或 CPU
+    pub fn validate(&self) -> Result<(), AccountError> {
+        let limits = [
+            ("m_cost", self.m_cost, KEYSTORE_KDF_MAX_M_COST),
+            ("t_cost", self.t_cost, KEYSTORE_KDF_MAX_T_COST),
+            ("p_cost", self.p_cost, KEYSTORE_KDF_MAX_P_COST),
+        ];
+        for (name, value, max) in limits {
+            if value > max {
+                return Err(keystore_error(format!(
+                    "kdf {} {} exceeds the maximum {}",
+                    name, value, max
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfInfo {
+    pub name: String,
+    #[serde(flatten)]
+    pub params: KdfParams,
+    pub salt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherInfo {
+    pub name: String,
+    pub nonce: String,
+}
+
+/// 加密钱包文件：Argon2id 派生密钥 + XChaCha20-Poly1305 加密明文 JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub kdf: KdfInfo,
+    pub cipher: CipherInfo,
+    pub ciphertext: String,
+}
+
+fn keystore_error<E: ToString>(e: E) -> AccountError {
+    AccountError::Keystore(e.to_string())
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    params: &KdfParams,
+) -> Result<Zeroizing<[u8; KEYSTORE_KEY_SIZE]>, AccountError> {
+    let params = Params::new(
+        params.m_cost,
+        params.t_cost,
+        params.p_cost,
+        Some(KEYSTORE_KEY_SIZE),
+    )
+    .map_err(keystore_error)?;
+    let mut key = Zeroizing::new([0u8; KEYSTORE_KEY_SIZE]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(keystore_error)?;
+    Ok(key)
+}
+
+impl Keystore {
+    pub fn encrypt(plaintext: &[u8], password: &str) -> Result<Self, AccountError> {
+        Keystore::encrypt_with_params(plaintext, password, KdfParams::default())
+    }
+
+    pub fn encrypt_with_params(
+        plaintext: &[u8],
+        password: &str,
+        params: KdfParams,
+    ) -> Result<Self, AccountError> {
+        // 超出上限的文件无法再解密，写入前同样检查
+        params.validate()?;
+        let mut salt = [0u8; KEYSTORE_SALT_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        let key = derive_key(password, &salt, &params)?;
+
+        let cipher = XChaCha20Poly1305::new((&*key).into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher.encrypt(&nonce, plaintext).map_err(keystore_error)?;
+
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            kdf: KdfInfo {
+                name: KEYSTORE_KDF.to_string(),
+                params,
+                salt: hex::encode(salt),
+            },
+            cipher: CipherInfo {
+                name: KEYSTORE_CIPHER.to_string(),
+                nonce: hex::encode(nonce),
+            },
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// 解密得到的明文在释放时清零
+    pub fn decrypt(&self, password: &str) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(keystore_error(format!(
+                "Unsupported keystore version: {}",
+                self.version
+            )));
+        }
+        if self.kdf.name != KEYSTORE_KDF {
+            return Err(keystore_error(format!(
+                "Unsupported kdf: {}",
+                self.kdf.name
+            )));
+        }
+        if self.cipher.name != KEYSTORE_CIPHER {
+            return Err(keystore_error(format!(
+                "Unsupported cipher: {}",
+                self.cipher.name
+            )));
+        }
+
+        self.kdf.params.validate()?;
+        let salt = hex::decode(&self.kdf.salt).map_err(keystore_error)?;
+        let nonce = hex::decode(&self.cipher.nonce).map_err(keystore_error)?;
+        if nonce.len() != 24 {
+            return Err(keystore_error("Invalid nonce length"));
+        }
+        let ciphertext = hex::decode(&self.ciphertext).map_err(keystore_error)?;
+
+        let key = derive_key(password, &salt, &self.kdf.params)?;
+        let cipher = XChaCha20Poly1305::new((&*key).into());
+        cipher
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_ref())
+            .map(Zeroizing::new)
+            .map_err(|_| keystore_error("Wrong password or corrupted keystore"))
+    }
+
+    /// 判断 JSON 是否为加密钱包（明文钱包没有 kdf / cipher 字段）
+    pub fn is_keystore(json: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(json)
+            .map(|v| v.get("kdf").is_some() && v.get("cipher").is_some())
+            .unwrap_or(false)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, AccountError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, AccountError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PARAMS: KdfParams = KdfParams {
+        m_cost: 1024,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    #[test]
+    fn test_keystore_encrypt_decrypt() {
+        let plaintext =
+            b"legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let keystore =
+            Keystore::encrypt_with_params(plaintext, "password", TEST_PARAMS).expect("加密失败");
+
+        let json = keystore.to_json().unwrap();
+        assert!(Keystore::is_keystore(&json));
+        assert!(!json.contains("legal"));
+
+        let loaded = Keystore::from_json(&json).unwrap();
+        assert_eq!(loaded.kdf.params, TEST_PARAMS);
+        assert_eq!(
+            loaded.decrypt("password").expect("解密失败").as_slice(),
+            plaintext
+        );
+        assert!(loaded.decrypt("wrong").is_err());
+    }
+
+    #[test]
+    fn test_keystore_rejects_unknown_version() {
+        let mut keystore = Keystore::encrypt_with_params(b"data", "pw", TEST_PARAMS).unwrap();
+        keystore.version = KEYSTORE_VERSION + 1;
+        assert!(keystore.decrypt("pw").is_err());
+        assert!(!Keystore::is_keystore("{\"info\":{}}"));
+    }
+
+    #[test]
+    fn test_keystore_rejects_excessive_kdf_params() {
+        let keystore = Keystore::encrypt_with_params(b"data", "pw", TEST_PARAMS).unwrap();
+        let excessive = [
+            KdfParams {
+                m_cost: KEYSTORE_KDF_MAX_M_COST + 1,
+                ..TEST_PARAMS
+            },
+            KdfParams {
+                t_cost: u32::MAX,
+                ..TEST_PARAMS
+            },
+            KdfParams {
+                p_cost: KEYSTORE_KDF_MAX_P_COST + 1,
+                ..TEST_PARAMS
+            },
+        ];
+        for params in excessive {
+            // 篡改后的文件在派生密钥前即被拒绝
+            let mut tampered = keystore.clone();
+            tampered.kdf.params = params;
+            let err = tampered.decrypt("pw").unwrap_err().to_string();
+            assert!(err.contains("exceeds the maximum"), "{}", err);
+            assert!(Keystore::encrypt_with_params(b"data", "pw", params).is_err());
+        }
+        assert!(KdfParams::default().validate().is_ok());
+    }
+}
@@ -1,4 +1,21 @@
+pub mod account;
 pub mod address;
+pub mod backup;
+pub mod bip85;
 pub mod consts;
+pub mod ecies;
+pub mod entropy;
+pub mod error;
 pub mod jni;
+pub mod keystore;
+pub mod message;
+pub mod mnemonic;
+pub mod multisig;
+pub mod public_address;
+pub mod slip39;
+pub mod storage;
+#[cfg(test)]
+mod test_utils;
+pub mod vanity;
 pub mod wallet;
+pub mod watch_only;
@@ -0,0 +1,299 @@
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bitcoin::address::{AddressType, NetworkUnchecked};
+use bitcoin::hashes::Hash;
+use bitcoin::sign_message::signed_msg_hash;
+use bitcoin::{Address, Network, PublicKey};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1};
+
+use crate::address::FreeWebMovementAddress;
+use crate::consts::{
+    BIP137_HEADER_P2PKH, BIP137_HEADER_P2PKH_UNCOMPRESSED, BIP137_HEADER_P2SH_P2WPKH,
+    BIP137_HEADER_P2WPKH, COIN_PREFIX,
+};
+use crate::error::AccountError;
+
+/// BIP137 可恢复签名：1 字节头 + 64 字节 compact 签名
+/// 字段私有，只能经 new / from_bytes 构造，保证地址类型与压缩标志能编码成签名头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageSignature {
+    signature: RecoverableSignature,
+    /// 签名对应的地址类型：P2pkh、P2sh（P2SH-P2WPKH）或 P2wpkh
+    address_type: AddressType,
+    compressed: bool,
+}
+
+impl MessageSignature {
+    /// 构造签名；地址类型与压缩标志没有对应的 BIP137 签名头时返回 UnsupportedAddressType
+    pub fn new(
+        signature: RecoverableSignature,
+        address_type: AddressType,
+        compressed: bool,
+    ) -> Result<Self, AccountError> {
+        MessageSignature::header_base(address_type, compressed)?;
+        Ok(MessageSignature {
+            signature,
+            address_type,
+            compressed,
+        })
+    }
+
+    pub fn signature(&self) -> &RecoverableSignature {
+        &self.signature
+    }
+
+    pub fn address_type(&self) -> AddressType {
+        self.address_type
+    }
+
+    pub fn compressed(&self) -> bool {
+        self.compressed
+    }
+
+    fn header_base(address_type: AddressType, compressed: bool) -> Result<u8, AccountError> {
+        match (address_type, compressed) {
+            (AddressType::P2pkh, false) => Ok(BIP137_HEADER_P2PKH_UNCOMPRESSED),
+            (AddressType::P2pkh, true) => Ok(BIP137_HEADER_P2PKH),
+            (AddressType::P2sh, true) => Ok(BIP137_HEADER_P2SH_P2WPKH),
+            (AddressType::P2wpkh, true) => Ok(BIP137_HEADER_P2WPKH),
+            (other, _) => Err(AccountError::UnsupportedAddressType(other.to_string())),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let (recovery_id, compact) = self.signature.serialize_compact();
+        let base = MessageSignature::header_base(self.address_type, self.compressed)
+            .expect("address type checked by MessageSignature::new");
+        let mut bytes = [0u8; 65];
+        bytes[0] = base + recovery_id.to_i32() as u8;
+        bytes[1..].copy_from_slice(&compact);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AccountError> {
+        if bytes.len() != 65 {
+            return Err(AccountError::Signature(secp256k1::Error::InvalidSignature));
+        }
+        let (address_type, compressed, base) = match bytes[0] {
+            27..=30 => (AddressType::P2pkh, false, BIP137_HEADER_P2PKH_UNCOMPRESSED),
+            31..=34 => (AddressType::P2pkh, true, BIP137_HEADER_P2PKH),
+            35..=38 => (AddressType::P2sh, true, BIP137_HEADER_P2SH_P2WPKH),
+            39..=42 => (AddressType::P2wpkh, true, BIP137_HEADER_P2WPKH),
+            _ => return Err(AccountError::Signature(secp256k1::Error::InvalidRecoveryId)),
+        };
+        let recovery_id = RecoveryId::from_i32((bytes[0] - base) as i32)?;
+        let signature = RecoverableSignature::from_compact(&bytes[1..], recovery_id)?;
+        Ok(MessageSignature {
+            signature,
+            address_type,
+            compressed,
+        })
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.to_bytes())
+    }
+
+    pub fn from_base64(s: &str) -> Result<Self, AccountError> {
+        MessageSignature::from_bytes(&STANDARD.decode(s)?)
+    }
+
+    /// 从签名中恢复签名者公钥
+    pub fn recover_public_key(&self, msg: &str) -> Result<PublicKey, AccountError> {
+        let secp = Secp256k1::verification_only();
+        let hash = signed_msg_hash(msg);
+        let message = Message::from_digest(hash.to_byte_array());
+        let key = secp.recover_ecdsa(&message, &self.signature)?;
+        Ok(PublicKey {
+            inner: key,
+            compressed: self.compressed,
+        })
+    }
+}
+
+impl FreeWebMovementAddress {
+    /// 以 "Bitcoin Signed Message" 格式签名，返回 Base64 编码的 65 字节签名
+    pub fn sign_bitcoin_message(&self, msg: &str) -> Result<String, AccountError> {
+        let address_type = self.info.address_type;
+        // 先检查地址类型，不支持时不必签名
+        MessageSignature::header_base(address_type, self.public_key.compressed)?;
+
+        let secp = Secp256k1::signing_only();
+        let hash = signed_msg_hash(msg);
+        let message = Message::from_digest(hash.to_byte_array());
+        let signature = secp.sign_ecdsa_recoverable(&message, &self.private_key.inner);
+        Ok(MessageSignature::new(signature, address_type, self.public_key.compressed)?.to_base64())
+    }
+
+    /// 按地址验证签名：恢复公钥，按签名头的地址类型生成地址并与给定地址比较
+    /// address 可以带 `FWMC:Zz:` 前缀，也可以是裸地址
+    pub fn verify_bitcoin_message(
+        address: &str,
+        msg: &str,
+        signature: &str,
+    ) -> Result<bool, AccountError> {
+        let prefix = format!("{}:", COIN_PREFIX);
+        let address = address.strip_prefix(&prefix).unwrap_or(address);
+        let expected: Address<NetworkUnchecked> = Address::from_str(address)?;
+
+        let signature = MessageSignature::from_base64(signature)?;
+        let public_key = signature.recover_public_key(msg)?;
+        // scriptPubKey 与网络无关，直接比较即可
+        let recovered = FreeWebMovementAddress::try_key_to_inner_address(
+            public_key,
+            Network::Bitcoin,
+            signature.address_type(),
+        )?;
+        Ok(recovered.script_pubkey() == expected.assume_checked_ref().script_pubkey())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_address;
+
+    fn address_of(address_type: AddressType) -> FreeWebMovementAddress {
+        fixture_address(Network::Bitcoin, address_type)
+    }
+
+    #[test]
+    fn test_bip137_sign_and_verify() {
+        let msg = "I own this address";
+        for (address_type, header) in [
+            (AddressType::P2pkh, BIP137_HEADER_P2PKH),
+            (AddressType::P2sh, BIP137_HEADER_P2SH_P2WPKH),
+            (AddressType::P2wpkh, BIP137_HEADER_P2WPKH),
+        ] {
+            let fwmaddress = address_of(address_type);
+            let signature = fwmaddress.sign_bitcoin_message(msg).expect("签名失败");
+            let bytes = STANDARD.decode(&signature).unwrap();
+            assert_eq!(bytes.len(), 65);
+            assert!((header..header + 4).contains(&bytes[0]));
+
+            let display = fwmaddress.to_string();
+            let bare = fwmaddress.address.to_string();
+            assert!(
+                FreeWebMovementAddress::verify_bitcoin_message(&display, msg, &signature).unwrap()
+            );
+            assert!(
+                FreeWebMovementAddress::verify_bitcoin_message(&bare, msg, &signature).unwrap()
+            );
+            assert!(
+                !FreeWebMovementAddress::verify_bitcoin_message(&display, "other", &signature)
+                    .unwrap()
+            );
+
+            let other = FreeWebMovementAddress::random().to_string();
+            assert!(
+                !FreeWebMovementAddress::verify_bitcoin_message(&other, msg, &signature).unwrap()
+            );
+        }
+
+        assert!(
+            address_of(AddressType::P2tr)
+                .sign_bitcoin_message(msg)
+                .is_err()
+        );
+        assert!(MessageSignature::from_base64("AAAA").is_err());
+
+        // 构造函数拒绝没有签名头的组合
+        let parsed = MessageSignature::from_base64(
+            &address_of(AddressType::P2wpkh)
+                .sign_bitcoin_message(msg)
+                .unwrap(),
+        )
+        .unwrap();
+        let recoverable = *parsed.signature();
+        assert!(MessageSignature::new(recoverable, AddressType::P2tr, true).is_err());
+        assert!(MessageSignature::new(recoverable, AddressType::P2wpkh, false).is_err());
+        assert_eq!(
+            MessageSignature::new(recoverable, AddressType::P2wpkh, true).unwrap(),
+            parsed
+        );
+    }
+
+    #[test]
+    fn test_bip137_rejects_tampering() {
+        let msg = "I own this address";
+        let fwmaddress = address_of(AddressType::P2pkh);
+        let signature = fwmaddress.sign_bitcoin_message(msg).unwrap();
+        let display = fwmaddress.to_string();
+        let verify = |bytes: &[u8]| {
+            FreeWebMovementAddress::verify_bitcoin_message(&display, msg, &STANDARD.encode(bytes))
+        };
+        let bytes = STANDARD.decode(&signature).unwrap();
+
+        // 篡改签名内容：恢复出其他公钥或签名无效
+        let mut tampered = bytes.clone();
+        tampered[10] ^= 1;
+        assert!(!verify(&tampered).unwrap_or(false));
+
+        // 签名头改为其他地址类型，恢复出的地址与 P2pkh 地址不符
+        let mut tampered = bytes.clone();
+        tampered[0] = tampered[0] - BIP137_HEADER_P2PKH + BIP137_HEADER_P2WPKH;
+        assert!(!verify(&tampered).unwrap());
+
+        // 无效的签名头与长度
+        let mut tampered = bytes.clone();
+        tampered[0] = 43;
+        assert!(matches!(verify(&tampered), Err(AccountError::Signature(_))));
+        assert!(verify(&bytes[..64]).is_err());
+    }
+
+    #[test]
+    fn test_bip137_address_type_and_network() {
+        let msg = "I own this address";
+
+        // 同一私钥的其他类型地址不能通过验证
+        let p2pkh = address_of(AddressType::P2pkh);
+        let p2wpkh = address_of(AddressType::P2wpkh);
+        let signature = p2pkh.sign_bitcoin_message(msg).unwrap();
+        assert!(
+            !FreeWebMovementAddress::verify_bitcoin_message(&p2wpkh.to_string(), msg, &signature)
+                .unwrap()
+        );
+
+        // 签名不包含网络信息：按 scriptPubKey 比较，同一公钥的测试网地址同样有效
+        let testnet = fixture_address(Network::Testnet, AddressType::P2wpkh);
+        let signature = testnet.sign_bitcoin_message(msg).unwrap();
+        assert!(
+            FreeWebMovementAddress::verify_bitcoin_message(&testnet.to_string(), msg, &signature)
+                .unwrap()
+        );
+        assert!(
+            FreeWebMovementAddress::verify_bitcoin_message(&p2wpkh.to_string(), msg, &signature)
+                .unwrap()
+        );
+        let other = fixture_address(Network::Testnet, AddressType::P2pkh);
+        assert!(
+            !FreeWebMovementAddress::verify_bitcoin_message(&other.to_string(), msg, &signature)
+                .unwrap()
+        );
+
+        // 无法解析的地址报错
+        assert!(
+            FreeWebMovementAddress::verify_bitcoin_message("ltc1qxyz", msg, &signature).is_err()
+        );
+    }
+
+    #[test]
+    fn test_bip137_compatible_with_bitcoin_crate() {
+        // 与 rust-bitcoin 的 P2PKH 消息签名实现互通
+        let fwmaddress = address_of(AddressType::P2pkh);
+        let msg = "Hello, FWM!";
+        let signature = fwmaddress.sign_bitcoin_message(msg).unwrap();
+        let bytes = STANDARD.decode(&signature).unwrap();
+
+        let secp = Secp256k1::verification_only();
+        let parsed = bitcoin::sign_message::MessageSignature::from_slice(&bytes).unwrap();
+        assert!(
+            parsed
+                .is_signed_by_address(&secp, &fwmaddress.address, signed_msg_hash(msg))
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use bip39::{Language, Mnemonic};
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::address::{FreeWebMovementAddress, MnemonicInfo};
+
+use crate::consts::{MNEMONIC_MAX_EDIT_DISTANCE, MNEMONIC_SUGGESTION_LIMIT, MNEMONIC_WORD_COUNTS};
+use crate::error::AccountError;
+
+/// 语言名称，用于命令行、JNI 和钱包文件
+pub fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::English => "english",
+        Language::SimplifiedChinese => "simplified-chinese",
+        Language::TraditionalChinese => "traditional-chinese",
+        Language::Czech => "czech",
+        Language::French => "french",
+        Language::Italian => "italian",
+        Language::Japanese => "japanese",
+        Language::Korean => "korean",
+        Language::Portuguese => "portuguese",
+        Language::Spanish => "spanish",
+    }
+}
+
+pub fn parse_language(name: &str) -> Result<Language, AccountError> {
+    let name = name.trim().to_lowercase();
+    Language::ALL
+        .iter()
+        .copied()
+        .find(|l| language_name(*l) == name)
+        .ok_or(AccountError::UnsupportedLanguage(name))
+}
+
+/// 自动识别助记词语言；多个词表都包含全部单词且校验和都有效时（如只含简繁共用汉字的中文助记词）
+/// 无法确定，返回 AmbiguousLanguages，由调用方指定语言
+pub fn detect_language(phrase: &str) -> Result<Language, AccountError> {
+    detect(phrase, None)
+}
+
+/// 同 detect_language，无法确定时若 preferred 在候选语言中则采用 preferred
+pub fn detect_language_preferring(
+    phrase: &str,
+    preferred: Language,
+) -> Result<Language, AccountError> {
+    detect(phrase, Some(preferred))
+}
+
+fn detect(phrase: &str, preferred: Option<Language>) -> Result<Language, AccountError> {
+    let mut cow = Cow::Borrowed(phrase);
+    Mnemonic::normalize_utf8_cow(&mut cow);
+    match Mnemonic::language_of(&cow) {
+        Ok(language) => Ok(language),
+        Err(bip39::Error::AmbiguousLanguages(candidates)) => {
+            let valid: Vec<Language> = candidates
+                .iter()
+                .filter(|l| Mnemonic::parse_in_normalized(*l, &cow).is_ok())
+                .collect();
+            match valid.as_slice() {
+                [] => Err(bip39::Error::InvalidChecksum.into()),
+                [language] => Ok(*language),
+                _ => preferred
+                    .filter(|l| valid.contains(l))
+                    .ok_or(bip39::Error::AmbiguousLanguages(candidates).into()),
+            }
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 用另一种语言的词表重新编码同一份熵
+pub fn translate_mnemonic(
+    mnemonic: &Mnemonic,
+    language: Language,
+) -> Result<Mnemonic, AccountError> {
+    let entropy = Zeroizing::new(mnemonic.to_entropy());
+    Ok(Mnemonic::from_entropy_in(language, &entropy)?)
+}
+
+/// 助记词翻译结果
+pub struct MnemonicTranslation {
+    pub mnemonic: Mnemonic,
+    /// 翻译后的助记词按原派生信息得到的地址
+    pub address: FreeWebMovementAddress,
+    /// BIP39 种子由单词本身派生，单词变化时地址也随之变化
+    pub address_changed: bool,
+}
+
+impl FreeWebMovementAddress {
+    /// 将钱包助记词翻译为另一种语言；passphrase 仅用于派生新地址
+    pub fn translate_mnemonic(
+        &self,
+        language: Language,
+        passphrase: &str,
+    ) -> Result<MnemonicTranslation, AccountError> {
+        let mnemonic = translate_mnemonic(&self.mnemonic, language)?;
+        let mnemonic_info = MnemonicInfo {
+            language,
+            word_count: mnemonic.word_count(),
+            phrase: mnemonic.to_string().into(),
+            passphrase: passphrase.to_string().into(),
+        };
+        let address = FreeWebMovementAddress::try_new(mnemonic_info, Some(self.info.clone()))?;
+        // 种子只取决于单词串和 passphrase，单词串相同（如简繁中文共用的字）则地址不变
+        let address_changed =
+            *Zeroizing::new(mnemonic.to_string()) != *Zeroizing::new(self.mnemonic.to_string());
+        Ok(MnemonicTranslation {
+            mnemonic,
+            address,
+            address_changed,
+        })
+    }
+}
+
+/// 不在词表中的单词及替换建议
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WordIssue {
+    /// 从 0 开始的单词位置
+    pub index: usize,
+    pub word: String,
+    pub suggestions: Vec<String>,
+}
+
+/// 助记词检查结果；只有单词数正确且全部单词有效时才检查校验和
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MnemonicReport {
+    pub word_count: usize,
+    pub invalid_word_count: bool,
+    pub unknown_words: Vec<WordIssue>,
+    /// 单词和数量都正确，仅校验和错误
+    pub invalid_checksum: bool,
+}
+
+impl MnemonicReport {
+    pub fn is_valid(&self) -> bool {
+        !self.invalid_word_count && self.unknown_words.is_empty() && !self.invalid_checksum
+    }
+}
+
+impl fmt::Display for MnemonicReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "mnemonic is valid");
+        }
+        let mut lines = Vec::new();
+        if self.invalid_word_count {
+            lines.push(format!(
+                "word count {} is invalid, expected one of {:?}",
+                self.word_count, MNEMONIC_WORD_COUNTS
+            ));
+        }
+        for issue in &self.unknown_words {
+            lines.push(format!(
+                "word {} \"{}\" is not in the wordlist, did you mean: {}",
+                issue.index + 1,
+                issue.word,
+                issue.suggestions.join(", ")
+            ));
+        }
+        if self.invalid_checksum {
+            lines.push("all words are valid but the checksum does not match".to_string());
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// 编辑距离（含相邻字符交换），输错顺序是最常见的抄写错误
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// 先按前缀补全，再按编辑距离由近到远补充，最多返回 MNEMONIC_SUGGESTION_LIMIT 个
+pub fn suggest_words(language: Language, word: &str) -> Vec<&'static str> {
+    let mut suggestions: Vec<&'static str> = language
+        .words_by_prefix(word)
+        .iter()
+        .copied()
+        .take(MNEMONIC_SUGGESTION_LIMIT)
+        .collect();
+    if suggestions.len() < MNEMONIC_SUGGESTION_LIMIT {
+        let mut near: Vec<(usize, &'static str)> = language
+            .word_list()
+            .iter()
+            .map(|w| (edit_distance(word, w), *w))
+            .filter(|(d, w)| *d <= MNEMONIC_MAX_EDIT_DISTANCE && !suggestions.contains(w))
+            .collect();
+        near.sort_by_key(|(d, _)| *d);
+        suggestions.extend(
+            near.into_iter()
+                .map(|(_, w)| w)
+                .take(MNEMONIC_SUGGESTION_LIMIT - suggestions.len()),
+        );
+    }
+    suggestions
+}
+
+/// 检查部分或完整的助记词：单词数、未知单词及建议、校验和
+pub fn validate_mnemonic(phrase: &str, language: Language) -> MnemonicReport {
+    let mut cow = Cow::Owned(phrase.to_lowercase());
+    Mnemonic::normalize_utf8_cow(&mut cow);
+    let words: Vec<&str> = cow.split_whitespace().collect();
+
+    let unknown_words: Vec<WordIssue> = words
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| language.find_word(w).is_none())
+        .map(|(index, w)| WordIssue {
+            index,
+            word: w.to_string(),
+            suggestions: suggest_words(language, w)
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+        .collect();
+    let invalid_word_count = !MNEMONIC_WORD_COUNTS.contains(&words.len());
+    let invalid_checksum = !invalid_word_count
+        && unknown_words.is_empty()
+        && matches!(
+            Mnemonic::parse_in_normalized(language, &cow),
+            Err(bip39::Error::InvalidChecksum)
+        );
+
+    MnemonicReport {
+        word_count: words.len(),
+        invalid_word_count,
+        unknown_words,
+        invalid_checksum,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_mnemonic() {
+        let valid = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let report = validate_mnemonic(valid, Language::English);
+        assert!(report.is_valid());
+        assert_eq!(report.to_string(), "mnemonic is valid");
+
+        // 拼写错误与不完整的单词
+        let report = validate_mnemonic(
+            "legal winner thank year wave sausage worht useful legal winner thank yel",
+            Language::English,
+        );
+        assert!(!report.is_valid());
+        assert!(!report.invalid_word_count);
+        assert_eq!(report.unknown_words.len(), 2);
+        assert_eq!(report.unknown_words[0].index, 6);
+        assert!(
+            report.unknown_words[0]
+                .suggestions
+                .contains(&"worth".to_string())
+        );
+        assert_eq!(report.unknown_words[1].index, 11);
+        assert!(
+            report.unknown_words[1]
+                .suggestions
+                .contains(&"yellow".to_string())
+        );
+        assert!(!report.invalid_checksum);
+
+        // 只有校验和错误
+        let report = validate_mnemonic(
+            "legal winner thank year wave sausage worth useful legal winner thank thank",
+            Language::English,
+        );
+        assert!(report.invalid_checksum);
+        assert!(report.unknown_words.is_empty());
+        assert!(report.to_string().contains("checksum"));
+
+        // 部分输入只报告单词数
+        let report = validate_mnemonic("Legal Winner", Language::English);
+        assert!(report.invalid_word_count);
+        assert!(report.unknown_words.is_empty());
+        assert!(!report.invalid_checksum);
+    }
+
+    #[test]
+    fn test_detect_language() {
+        for language in Language::ALL {
+            let mnemonic = Mnemonic::generate_in(*language, 12).unwrap();
+            let detected = detect_language_preferring(&mnemonic.to_string(), *language).unwrap();
+            assert_eq!(detected, *language);
+        }
+        assert!(detect_language("not a mnemonic").is_err());
+    }
+
+    #[test]
+    fn test_detect_language_ambiguous_chinese() {
+        // 只用简繁共用的汉字，找一个在两种词表中校验和都有效的助记词
+        let simplified = Language::SimplifiedChinese.word_list();
+        let shared: Vec<&str> = Language::TraditionalChinese
+            .word_list()
+            .iter()
+            .copied()
+            .filter(|w| simplified.contains(w))
+            .collect();
+        let prefix = shared[..11].join(" ");
+        let phrase = shared[11..]
+            .iter()
+            .map(|last| format!("{} {}", prefix, last))
+            .find(|phrase| {
+                Mnemonic::parse_in(Language::SimplifiedChinese, phrase.as_str()).is_ok()
+                    && Mnemonic::parse_in(Language::TraditionalChinese, phrase.as_str()).is_ok()
+            })
+            .expect("共用汉字足以组成两种语言都有效的助记词");
+
+        assert!(matches!(
+            detect_language(&phrase),
+            Err(AccountError::Mnemonic(bip39::Error::AmbiguousLanguages(_)))
+        ));
+        assert!(MnemonicInfo::from_phrase(&phrase, "").is_err());
+        for language in [Language::SimplifiedChinese, Language::TraditionalChinese] {
+            assert_eq!(
+                detect_language_preferring(&phrase, language).unwrap(),
+                language
+            );
+        }
+        assert!(detect_language_preferring(&phrase, Language::English).is_err());
+
+        // 调用方指定语言后，保存再读取仍是同一种语言
+        let mnemonic_info = MnemonicInfo {
+            language: Language::TraditionalChinese,
+            word_count: 12,
+            phrase: phrase.clone().into(),
+            passphrase: String::new().into(),
+        };
+        let address = FreeWebMovementAddress::try_new(mnemonic_info, None).unwrap();
+        let json = serde_json::to_string(&address).unwrap();
+        let loaded = FreeWebMovementAddress::try_from_json(&json).unwrap();
+        assert_eq!(loaded.mnemonic.language(), Language::TraditionalChinese);
+        assert_eq!(
+            loaded.mnemonic_meta().language,
+            Language::TraditionalChinese
+        );
+    }
+
+    #[test]
+    fn test_translate_mnemonic() {
+        let original = FreeWebMovementAddress::random();
+        let translation = original
+            .translate_mnemonic(Language::SimplifiedChinese, "")
+            .unwrap();
+        assert_eq!(translation.mnemonic.language(), Language::SimplifiedChinese);
+        assert_eq!(
+            translation.mnemonic.to_entropy(),
+            original.mnemonic.to_entropy()
+        );
+        assert!(translation.address_changed);
+        assert_ne!(translation.address.address, original.address);
+
+        // 翻译回原语言即可恢复原地址
+        let back = translation
+            .address
+            .translate_mnemonic(Language::English, "")
+            .unwrap();
+        assert_eq!(back.mnemonic, original.mnemonic);
+        assert_eq!(back.address.to_string(), original.to_string());
+
+        let same = original.translate_mnemonic(Language::English, "").unwrap();
+        assert!(!same.address_changed);
+    }
+
+    #[test]
+    fn test_language_names() {
+        for language in Language::ALL {
+            assert_eq!(parse_language(language_name(*language)).unwrap(), *language);
+        }
+        assert!(parse_language("klingon").is_err());
+        assert_eq!(edit_distance("worht", "worth"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(suggest_words(Language::English, "abandom")[0], "abandon");
+    }
+}
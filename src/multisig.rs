@@ -0,0 +1,207 @@
+use std::fmt;
+
+use bitcoin::blockdata::opcodes::all::OP_CHECKMULTISIG;
+use bitcoin::blockdata::script::{Builder, ScriptBuf};
+use bitcoin::{Address, Network, PublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::address::FreeWebMovementAddress;
+use crate::consts::{COIN_PREFIX, MULTISIG_MAX_KEYS};
+use crate::error::AccountError;
+
+/// 多签地址的封装方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultisigType {
+    /// 传统 P2SH，赎回脚本即多签脚本
+    P2sh,
+    /// 原生隔离见证 P2WSH，见证脚本即多签脚本
+    P2wsh,
+    /// P2SH 包裹的 P2WSH，兼容不支持 bech32 的钱包
+    P2shP2wsh,
+}
+
+/// m-of-n 多签地址，公钥按 BIP67 排序
+#[derive(Debug, Clone)]
+pub struct MultisigAddress {
+    pub threshold: usize,
+    pub public_keys: Vec<PublicKey>,
+    pub multisig_type: MultisigType,
+    pub network: Network,
+    pub prefix: String,
+    /// OP_m <pubkeys> OP_n OP_CHECKMULTISIG
+    pub script: ScriptBuf,
+    pub address: Address,
+}
+
+impl MultisigAddress {
+    pub fn new(
+        threshold: usize,
+        public_keys: Vec<PublicKey>,
+        network: Network,
+        multisig_type: MultisigType,
+    ) -> Result<Self, AccountError> {
+        let public_keys = MultisigAddress::sort_keys(public_keys);
+        let n = public_keys.len();
+        if n == 0 || n > MULTISIG_MAX_KEYS {
+            return Err(AccountError::Multisig(format!(
+                "Number of keys must be between 1 and {}, got {}",
+                MULTISIG_MAX_KEYS, n
+            )));
+        }
+        if threshold == 0 || threshold > n {
+            return Err(AccountError::Multisig(format!(
+                "Threshold must be between 1 and {}, got {}",
+                n, threshold
+            )));
+        }
+        if public_keys.windows(2).any(|w| w[0] == w[1]) {
+            return Err(AccountError::Multisig("Duplicate public key".to_string()));
+        }
+        if multisig_type != MultisigType::P2sh && public_keys.iter().any(|k| !k.compressed) {
+            return Err(AccountError::Multisig(
+                "Segwit multisig requires compressed public keys".to_string(),
+            ));
+        }
+
+        let script = MultisigAddress::multisig_script(threshold, &public_keys);
+        let address = match multisig_type {
+            MultisigType::P2sh => Address::p2sh(&script, network)?,
+            MultisigType::P2wsh => Address::p2wsh(&script, network),
+            MultisigType::P2shP2wsh => Address::p2shwsh(&script, network),
+        };
+
+        Ok(MultisigAddress {
+            threshold,
+            public_keys,
+            multisig_type,
+            network,
+            prefix: COIN_PREFIX.to_string(),
+            script,
+            address,
+        })
+    }
+
+    /// 使用多个钱包地址的公钥构造，网络取第一个地址的网络
+    pub fn from_addresses(
+        threshold: usize,
+        addresses: &[FreeWebMovementAddress],
+        multisig_type: MultisigType,
+    ) -> Result<Self, AccountError> {
+        let network = addresses
+            .first()
+            .map(|a| a.info.network)
+            .unwrap_or(Network::Bitcoin);
+        if addresses.iter().any(|a| a.info.network != network) {
+            return Err(AccountError::Multisig(
+                "All addresses must be on the same network".to_string(),
+            ));
+        }
+        let keys = addresses.iter().map(|a| a.public_key).collect();
+        MultisigAddress::new(threshold, keys, network, multisig_type)
+    }
+
+    /// 使用十六进制公钥构造
+    pub fn from_hex(
+        threshold: usize,
+        public_keys: &[&str],
+        network: Network,
+        multisig_type: MultisigType,
+    ) -> Result<Self, AccountError> {
+        let keys = public_keys
+            .iter()
+            .map(|k| FreeWebMovementAddress::try_to_public_key(&hex::decode(k)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        MultisigAddress::new(threshold, keys, network, multisig_type)
+    }
+
+    /// BIP67：按序列化后的公钥字节字典序排序
+    pub fn sort_keys(mut public_keys: Vec<PublicKey>) -> Vec<PublicKey> {
+        public_keys.sort_by_key(|k| k.to_bytes());
+        public_keys
+    }
+
+    pub fn multisig_script(threshold: usize, public_keys: &[PublicKey]) -> ScriptBuf {
+        let mut builder = Builder::new().push_int(threshold as i64);
+        for key in public_keys {
+            builder = builder.push_key(key);
+        }
+        builder
+            .push_int(public_keys.len() as i64)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script()
+    }
+
+    /// P2SH 的赎回脚本；P2SH-P2WSH 时为 0 <sha256(witness_script)>
+    pub fn redeem_script(&self) -> Option<ScriptBuf> {
+        match self.multisig_type {
+            MultisigType::P2sh => Some(self.script.clone()),
+            MultisigType::P2wsh => None,
+            MultisigType::P2shP2wsh => Some(ScriptBuf::new_p2wsh(&self.script.wscript_hash())),
+        }
+    }
+
+    pub fn witness_script(&self) -> Option<ScriptBuf> {
+        match self.multisig_type {
+            MultisigType::P2sh => None,
+            MultisigType::P2wsh | MultisigType::P2shP2wsh => Some(self.script.clone()),
+        }
+    }
+}
+
+impl fmt::Display for MultisigAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.prefix, self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip67_p2sh_vector() {
+        // BIP67 测试向量：未排序输入，2-of-2
+        let keys = [
+            "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+            "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+        ];
+        let multisig =
+            MultisigAddress::from_hex(2, &keys, Network::Bitcoin, MultisigType::P2sh).unwrap();
+        assert_eq!(hex::encode(multisig.public_keys[0].to_bytes()), keys[1]);
+        assert_eq!(
+            multisig.address.to_string(),
+            "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z"
+        );
+        assert_eq!(
+            multisig.to_string(),
+            format!("{}:39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z", COIN_PREFIX)
+        );
+        assert_eq!(multisig.redeem_script(), Some(multisig.script.clone()));
+        assert!(multisig.witness_script().is_none());
+    }
+
+    #[test]
+    fn test_multisig_from_addresses() {
+        let addresses: Vec<_> = (0..5).map(|_| FreeWebMovementAddress::random()).collect();
+
+        let p2wsh = MultisigAddress::from_addresses(3, &addresses, MultisigType::P2wsh).unwrap();
+        assert!(p2wsh.address.to_string().starts_with("bc1q"));
+        assert!(p2wsh.redeem_script().is_none());
+
+        let nested =
+            MultisigAddress::from_addresses(3, &addresses, MultisigType::P2shP2wsh).unwrap();
+        assert!(nested.address.to_string().starts_with('3'));
+        assert_eq!(nested.witness_script(), p2wsh.witness_script());
+
+        // 输入顺序不影响结果
+        let mut reversed = addresses.clone();
+        reversed.reverse();
+        let p2wsh2 = MultisigAddress::from_addresses(3, &reversed, MultisigType::P2wsh).unwrap();
+        assert_eq!(p2wsh.address, p2wsh2.address);
+
+        assert!(MultisigAddress::from_addresses(6, &addresses, MultisigType::P2sh).is_err());
+        assert!(MultisigAddress::from_addresses(0, &addresses, MultisigType::P2sh).is_err());
+        let duplicated = vec![addresses[0].clone(), addresses[0].clone()];
+        assert!(MultisigAddress::from_addresses(1, &duplicated, MultisigType::P2sh).is_err());
+    }
+}
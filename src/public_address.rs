@@ -0,0 +1,169 @@
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::address::{AddressType, NetworkUnchecked};
+use bitcoin::{Address, Network};
+
+use crate::address::FreeWebMovementAddress;
+use crate::consts::COIN_PREFIX;
+use crate::error::AccountError;
+
+/// 按顺序尝试的网络；Signet 与 Testnet 编码相同，Regtest 的 base58 地址也与 Testnet 相同，
+/// 这些情况统一识别为 Testnet
+const PARSE_NETWORKS: [Network; 3] = [Network::Bitcoin, Network::Testnet, Network::Regtest];
+
+/// 从 `FWMC:Zz:<address>` 字符串解析出的地址，只含公开信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicAddress {
+    pub prefix: String,
+    pub network: Network,
+    pub address_type: AddressType,
+    pub address: Address,
+}
+
+impl FromStr for PublicAddress {
+    type Err = AccountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .strip_prefix(COIN_PREFIX)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| AccountError::InvalidPrefix(s.to_string()))?;
+
+        // 格式和校验和错误由 bitcoin 的解析器报告
+        let unchecked: Address<NetworkUnchecked> = Address::from_str(inner)?;
+        let network = PARSE_NETWORKS
+            .into_iter()
+            .find(|n| unchecked.is_valid_for_network(*n))
+            .ok_or_else(|| AccountError::UnsupportedAddressType(inner.to_string()))?;
+        let address = unchecked.require_network(network)?;
+        let address_type = address
+            .address_type()
+            .ok_or_else(|| AccountError::UnsupportedAddressType(inner.to_string()))?;
+
+        Ok(PublicAddress {
+            prefix: COIN_PREFIX.to_string(),
+            network,
+            address_type,
+            address,
+        })
+    }
+}
+
+impl From<&FreeWebMovementAddress> for PublicAddress {
+    fn from(fwmaddress: &FreeWebMovementAddress) -> Self {
+        PublicAddress {
+            prefix: fwmaddress.info.prefix.clone(),
+            network: fwmaddress.info.network,
+            address_type: fwmaddress.info.address_type,
+            address: fwmaddress.address.clone(),
+        }
+    }
+}
+
+impl fmt::Display for PublicAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.prefix, self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_address;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for network in [Network::Bitcoin, Network::Testnet] {
+            for address_type in [
+                AddressType::P2pkh,
+                AddressType::P2sh,
+                AddressType::P2wpkh,
+                AddressType::P2tr,
+            ] {
+                let fwmaddress = fixture_address(network, address_type);
+                let parsed: PublicAddress = fwmaddress.to_string().parse().expect("解析失败");
+                assert_eq!(parsed, PublicAddress::from(&fwmaddress));
+                assert_eq!(parsed.network, network);
+                assert_eq!(parsed.address_type, address_type);
+                assert_eq!(parsed.to_string(), fwmaddress.to_string());
+            }
+        }
+
+        // bech32 地址可以区分 Regtest
+        let regtest = fixture_address(Network::Regtest, AddressType::P2wpkh);
+        let parsed: PublicAddress = regtest.to_string().parse().unwrap();
+        assert_eq!(parsed.network, Network::Regtest);
+        assert_eq!(parsed.address_type, AddressType::P2wpkh);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let fwmaddress = FreeWebMovementAddress::random();
+        let bare = fwmaddress.address.to_string();
+
+        for s in [
+            bare.clone(),
+            format!("FWMC:Xx:{}", bare),
+            format!("{}{}", COIN_PREFIX, bare),
+        ] {
+            assert!(matches!(
+                s.parse::<PublicAddress>(),
+                Err(AccountError::InvalidPrefix(_))
+            ));
+        }
+
+        // 篡改最后一个字符，校验和失败
+        let mut tampered = fwmaddress.to_string();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == '1' { '2' } else { '1' });
+        assert!(matches!(
+            tampered.parse::<PublicAddress>(),
+            Err(AccountError::AddressParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_network_and_type_edges() {
+        // Signet 与 Testnet 共用 tb 前缀，解析结果为 Testnet
+        let signet = fixture_address(Network::Signet, AddressType::P2wpkh);
+        let parsed: PublicAddress = signet.to_string().parse().unwrap();
+        assert_eq!(parsed.network, Network::Testnet);
+
+        // 主网地址不会被当作测试网地址
+        let mainnet = fixture_address(Network::Bitcoin, AddressType::P2wpkh);
+        let parsed: PublicAddress = mainnet.to_string().parse().unwrap();
+        assert_eq!(parsed.network, Network::Bitcoin);
+        assert!(
+            !parsed
+                .address
+                .as_unchecked()
+                .is_valid_for_network(Network::Testnet)
+        );
+
+        // 其他币种的 bech32 前缀无法解析
+        let ltc = format!(
+            "{}:ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7kgmn4n9",
+            COIN_PREFIX
+        );
+        assert!(matches!(
+            ltc.parse::<PublicAddress>(),
+            Err(AccountError::AddressParse(_))
+        ));
+
+        // 未定义的隔离见证版本（BIP350 测试向量，v2）没有对应的地址类型
+        let future = format!("{}:bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", COIN_PREFIX);
+        assert!(matches!(
+            future.parse::<PublicAddress>(),
+            Err(AccountError::UnsupportedAddressType(_))
+        ));
+
+        // P2WSH 地址按其实际类型识别
+        let p2wsh = format!(
+            "{}:bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+            COIN_PREFIX
+        );
+        let parsed: PublicAddress = p2wsh.parse().unwrap();
+        assert_eq!(parsed.address_type, AddressType::P2wsh);
+    }
+}
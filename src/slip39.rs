@@ -0,0 +1,706 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use bip39::{Language, Mnemonic};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::address::{AddressInfo, FreeWebMovementAddress, MnemonicInfo};
+use crate::consts::{
+    SLIP39_BASE_ITERATION_COUNT, SLIP39_CHECKSUM_WORDS, SLIP39_CUSTOMIZATION,
+    SLIP39_CUSTOMIZATION_EXTENDABLE, SLIP39_DIGEST_BYTES, SLIP39_DIGEST_INDEX,
+    SLIP39_ITERATION_EXPONENT, SLIP39_MAX_SHARE_COUNT, SLIP39_MIN_SECRET_BYTES, SLIP39_RADIX_BITS,
+    SLIP39_ROUND_COUNT, SLIP39_SECRET_INDEX,
+};
+use crate::error::AccountError;
+
+const WORDLIST: &str = include_str!("slip39_wordlist.txt");
+
+/// RS1024 校验和的生成多项式
+const RS1024_GEN: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+    0x21B1F890, 0x3F3F120,
+];
+
+/// 分片的 x 坐标和值
+type SharePoint = (u8, Zeroizing<Vec<u8>>);
+
+fn slip39_error<E: ToString>(e: E) -> AccountError {
+    AccountError::Slip39(e.to_string())
+}
+
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| WORDLIST.lines().collect())
+}
+
+/// GF(256) 的对数表和指数表，生成元为 3，约化多项式为 x^8 + x^4 + x^3 + x + 1
+fn gf256_tables() -> &'static ([u8; 255], [u8; 256]) {
+    static TABLES: OnceLock<([u8; 255], [u8; 256])> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut exp = [0u8; 255];
+        let mut log = [0u8; 256];
+        let mut poly: u16 = 1;
+        for (i, e) in exp.iter_mut().enumerate() {
+            *e = poly as u8;
+            log[poly as usize] = i as u8;
+            poly = (poly << 1) ^ poly;
+            if poly & 0x100 != 0 {
+                poly ^= 0x11B;
+            }
+        }
+        (exp, log)
+    })
+}
+
+/// 在 x 处对各分片做拉格朗日插值
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    if let Some((_, value)) = shares.iter().find(|(xi, _)| *xi == x) {
+        return Ok(Zeroizing::new(value.to_vec()));
+    }
+    let len = shares.first().map(|(_, v)| v.len()).unwrap_or(0);
+    if shares.iter().any(|(_, v)| v.len() != len) {
+        return Err(slip39_error("All share values must have the same length"));
+    }
+
+    let (exp, log) = gf256_tables();
+    let log_prod: i32 = shares
+        .iter()
+        .map(|(xi, _)| log[(xi ^ x) as usize] as i32)
+        .sum();
+    let mut result = Zeroizing::new(vec![0u8; len]);
+    for (xi, yi) in shares {
+        let others: i32 = shares
+            .iter()
+            .map(|(xj, _)| log[(xi ^ xj) as usize] as i32)
+            .sum();
+        let log_basis = (log_prod - log[(xi ^ x) as usize] as i32 - others).rem_euclid(255);
+        for (r, y) in result.iter_mut().zip(yi.iter()) {
+            if *y != 0 {
+                *r ^= exp[((log[*y as usize] as i32 + log_basis) % 255) as usize];
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn share_digest(random: &[u8], secret: &[u8]) -> [u8; SLIP39_DIGEST_BYTES] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random).expect("HMAC accepts keys of any length");
+    mac.update(secret);
+    let mut digest = [0u8; SLIP39_DIGEST_BYTES];
+    digest.copy_from_slice(&mac.finalize().into_bytes()[..SLIP39_DIGEST_BYTES]);
+    digest
+}
+
+/// 把秘密拆成 count 份，任意 threshold 份可恢复
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Result<Vec<SharePoint>, AccountError> {
+    if threshold == 0 || threshold > count {
+        return Err(slip39_error(format!(
+            "Threshold must be between 1 and {}, got {}",
+            count, threshold
+        )));
+    }
+    if threshold == 1 {
+        return Ok((0..count)
+            .map(|i| (i, Zeroizing::new(secret.to_vec())))
+            .collect());
+    }
+
+    let mut shares = Vec::new();
+    for i in 0..threshold - 2 {
+        let mut value = Zeroizing::new(vec![0u8; secret.len()]);
+        OsRng.fill_bytes(&mut value);
+        shares.push((i, value));
+    }
+    let mut digest_share = Zeroizing::new(vec![0u8; secret.len()]);
+    OsRng.fill_bytes(&mut digest_share[SLIP39_DIGEST_BYTES..]);
+    let digest = share_digest(&digest_share[SLIP39_DIGEST_BYTES..], secret);
+    digest_share[..SLIP39_DIGEST_BYTES].copy_from_slice(&digest);
+
+    let mut base: Vec<(u8, &[u8])> = shares.iter().map(|(i, v)| (*i, v.as_slice())).collect();
+    base.push((SLIP39_DIGEST_INDEX, &digest_share));
+    base.push((SLIP39_SECRET_INDEX, secret));
+    let mut rest = Vec::new();
+    for i in threshold - 2..count {
+        rest.push((i, interpolate(&base, i)?));
+    }
+    shares.extend(rest);
+    Ok(shares)
+}
+
+/// 由 threshold 份分片恢复秘密，并校验摘要
+fn recover_secret(
+    threshold: u8,
+    shares: &[(u8, &[u8])],
+) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    if threshold == 1 {
+        return Ok(Zeroizing::new(shares[0].1.to_vec()));
+    }
+    let secret = interpolate(shares, SLIP39_SECRET_INDEX)?;
+    let digest_share = interpolate(shares, SLIP39_DIGEST_INDEX)?;
+    if digest_share.len() < SLIP39_DIGEST_BYTES
+        || digest_share[..SLIP39_DIGEST_BYTES]
+            != share_digest(&digest_share[SLIP39_DIGEST_BYTES..], &secret)
+    {
+        return Err(slip39_error("Invalid digest of the shared secret"));
+    }
+    Ok(secret)
+}
+
+fn feistel_salt(identifier: u16, extendable: bool) -> Vec<u8> {
+    if extendable {
+        return Vec::new();
+    }
+    let mut salt = SLIP39_CUSTOMIZATION.as_bytes().to_vec();
+    salt.extend_from_slice(&identifier.to_be_bytes());
+    salt
+}
+
+/// 4 轮 Feistel 网络，轮函数为 PBKDF2-HMAC-SHA256；decrypt 时逆序执行各轮
+fn feistel(
+    input: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    decrypt: bool,
+) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    if !passphrase.bytes().all(|b| (32..=126).contains(&b)) {
+        return Err(slip39_error("Passphrase must contain only printable ASCII"));
+    }
+    let half = input.len() / 2;
+    let mut left = Zeroizing::new(input[..half].to_vec());
+    let mut right = Zeroizing::new(input[half..].to_vec());
+    let salt = feistel_salt(identifier, extendable);
+    let iterations =
+        (SLIP39_BASE_ITERATION_COUNT << iteration_exponent) / SLIP39_ROUND_COUNT as u32;
+
+    let rounds: Vec<u8> = if decrypt {
+        (0..SLIP39_ROUND_COUNT).rev().collect()
+    } else {
+        (0..SLIP39_ROUND_COUNT).collect()
+    };
+    for round in rounds {
+        let mut password = Zeroizing::new(vec![round]);
+        password.extend_from_slice(passphrase.as_bytes());
+        let mut round_salt = salt.clone();
+        round_salt.extend_from_slice(&right);
+        let mut f = Zeroizing::new(vec![0u8; half]);
+        pbkdf2::<Hmac<Sha256>>(&password, &round_salt, iterations, &mut f)
+            .expect("HMAC accepts keys of any length");
+        for (l, f) in left.iter_mut().zip(f.iter()) {
+            *l ^= f;
+        }
+        std::mem::swap(&mut left, &mut right);
+    }
+
+    let mut output = Zeroizing::new(Vec::with_capacity(input.len()));
+    output.extend_from_slice(&right);
+    output.extend_from_slice(&left);
+    Ok(output)
+}
+
+fn rs1024_polymod(values: impl Iterator<Item = u32>) -> u32 {
+    let mut chk: u32 = 1;
+    for v in values {
+        let b = chk >> 20;
+        chk = ((chk & 0xFFFFF) << 10) ^ v;
+        for (i, generator) in RS1024_GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn customization(extendable: bool) -> &'static str {
+    if extendable {
+        SLIP39_CUSTOMIZATION_EXTENDABLE
+    } else {
+        SLIP39_CUSTOMIZATION
+    }
+}
+
+fn checksum_values(extendable: bool) -> impl Iterator<Item = u32> {
+    customization(extendable).bytes().map(u32::from)
+}
+
+/// 单个 SLIP-39 分片
+#[derive(Clone)]
+pub struct Share {
+    pub identifier: u16,
+    pub extendable: bool,
+    pub iteration_exponent: u8,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+    pub value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// 同一组分片必须一致的公共参数
+    fn common_params(&self) -> (u16, bool, u8, u8, u8) {
+        (
+            self.identifier,
+            self.extendable,
+            self.iteration_exponent,
+            self.group_threshold,
+            self.group_count,
+        )
+    }
+
+    fn to_indices(&self) -> Vec<u32> {
+        let id_exp = ((self.identifier as u32) << 5)
+            | ((self.extendable as u32) << 4)
+            | self.iteration_exponent as u32;
+        let params = ((self.group_index as u32) << 16)
+            | ((self.group_threshold as u32 - 1) << 12)
+            | ((self.group_count as u32 - 1) << 8)
+            | ((self.member_index as u32) << 4)
+            | (self.member_threshold as u32 - 1);
+        let mut indices = vec![id_exp >> 10, id_exp & 0x3FF, params >> 10, params & 0x3FF];
+
+        // 分片值按 10 位一组编码，高位补零
+        let word_count = (self.value.len() * 8).div_ceil(SLIP39_RADIX_BITS);
+        let padding = word_count * SLIP39_RADIX_BITS - self.value.len() * 8;
+        let mut acc: u32 = 0;
+        let mut bits = padding;
+        for byte in self.value.iter() {
+            acc = (acc << 8) | *byte as u32;
+            bits += 8;
+            while bits >= SLIP39_RADIX_BITS {
+                bits -= SLIP39_RADIX_BITS;
+                indices.push((acc >> bits) & 0x3FF);
+            }
+            acc &= (1 << bits) - 1;
+        }
+
+        let polymod = rs1024_polymod(
+            checksum_values(self.extendable)
+                .chain(indices.iter().copied())
+                .chain([0; SLIP39_CHECKSUM_WORDS]),
+        ) ^ 1;
+        for i in 0..SLIP39_CHECKSUM_WORDS {
+            indices
+                .push((polymod >> (SLIP39_RADIX_BITS * (SLIP39_CHECKSUM_WORDS - 1 - i))) & 0x3FF);
+        }
+        indices
+    }
+
+    /// 编码为空格分隔的 SLIP-39 单词
+    pub fn to_words(&self) -> Zeroizing<String> {
+        let words = wordlist();
+        let phrase: Vec<&str> = self
+            .to_indices()
+            .into_iter()
+            .map(|i| words[i as usize])
+            .collect();
+        Zeroizing::new(phrase.join(" "))
+    }
+
+    pub fn from_words(phrase: &str) -> Result<Self, AccountError> {
+        let words = wordlist();
+        let indices = phrase
+            .split_whitespace()
+            .map(|w| {
+                let w = w.to_lowercase();
+                words
+                    .binary_search(&w.as_str())
+                    .map(|i| i as u32)
+                    .map_err(|_| slip39_error(format!("Unknown word: {}", w)))
+            })
+            .collect::<Result<Vec<u32>, _>>()
+            .map(Zeroizing::new)?;
+
+        let min_words =
+            4 + (SLIP39_MIN_SECRET_BYTES * 8).div_ceil(SLIP39_RADIX_BITS) + SLIP39_CHECKSUM_WORDS;
+        if indices.len() < min_words {
+            return Err(slip39_error(format!(
+                "Share must have at least {} words, got {}",
+                min_words,
+                indices.len()
+            )));
+        }
+
+        let extendable = (indices[1] >> 4) & 1 == 1;
+        if rs1024_polymod(checksum_values(extendable).chain(indices.iter().copied())) != 1 {
+            return Err(slip39_error("Invalid checksum"));
+        }
+
+        let identifier = ((indices[0] << 5) | (indices[1] >> 5)) as u16;
+        let iteration_exponent = (indices[1] & 0xF) as u8;
+        let params = (indices[2] << 10) | indices[3];
+        let group_index = (params >> 16) as u8;
+        let group_threshold = ((params >> 12) & 0xF) as u8 + 1;
+        let group_count = ((params >> 8) & 0xF) as u8 + 1;
+        let member_index = ((params >> 4) & 0xF) as u8;
+        let member_threshold = (params & 0xF) as u8 + 1;
+        if group_count < group_threshold {
+            return Err(slip39_error("Group threshold exceeds group count"));
+        }
+
+        let value_words = &indices[4..indices.len() - SLIP39_CHECKSUM_WORDS];
+        let padding = (value_words.len() * SLIP39_RADIX_BITS) % 16;
+        if padding > 8 {
+            return Err(slip39_error("Invalid share length"));
+        }
+        let mut value = Zeroizing::new(Vec::new());
+        let mut acc: u32 = 0;
+        let mut bits = 0;
+        let mut skip = padding;
+        for word in value_words {
+            acc = (acc << SLIP39_RADIX_BITS) | word;
+            bits += SLIP39_RADIX_BITS;
+            if skip > 0 {
+                if acc >> (bits - skip) != 0 {
+                    return Err(slip39_error("Invalid share padding"));
+                }
+                bits -= skip;
+                acc &= (1 << bits) - 1;
+                skip = 0;
+            }
+            while bits >= 8 {
+                bits -= 8;
+                value.push((acc >> bits) as u8);
+            }
+            acc &= (1 << bits) - 1;
+        }
+
+        Ok(Share {
+            identifier,
+            extendable,
+            iteration_exponent,
+            group_index,
+            group_threshold,
+            group_count,
+            member_index,
+            member_threshold,
+            value,
+        })
+    }
+}
+
+/// 把主秘密拆分为若干组分片
+/// groups 中每项为 (member_threshold, member_count)，恢复时需要 group_threshold 个组
+pub fn generate_shares(
+    master_secret: &[u8],
+    passphrase: &str,
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    iteration_exponent: u8,
+    extendable: bool,
+) -> Result<Vec<Vec<Share>>, AccountError> {
+    if master_secret.len() < SLIP39_MIN_SECRET_BYTES || !master_secret.len().is_multiple_of(2) {
+        return Err(slip39_error(format!(
+            "Master secret must be an even number of bytes, at least {}",
+            SLIP39_MIN_SECRET_BYTES
+        )));
+    }
+    if groups.is_empty() || groups.len() > SLIP39_MAX_SHARE_COUNT {
+        return Err(slip39_error(format!(
+            "Number of groups must be between 1 and {}",
+            SLIP39_MAX_SHARE_COUNT
+        )));
+    }
+    if iteration_exponent > 0xF {
+        return Err(slip39_error("Iteration exponent must be at most 15"));
+    }
+    for (member_threshold, member_count) in groups {
+        if *member_count as usize > SLIP39_MAX_SHARE_COUNT {
+            return Err(slip39_error(format!(
+                "Number of shares in a group must be at most {}",
+                SLIP39_MAX_SHARE_COUNT
+            )));
+        }
+        if *member_threshold == 1 && *member_count > 1 {
+            return Err(slip39_error(
+                "Member threshold 1 with multiple shares is not allowed, use 1-of-1",
+            ));
+        }
+    }
+
+    let identifier = (OsRng.next_u32() & 0x7FFF) as u16;
+    let encrypted = feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        false,
+    )?;
+
+    let group_secrets = split_secret(group_threshold, groups.len() as u8, &encrypted)?;
+    let mut result = Vec::new();
+    for ((group_index, group_secret), (member_threshold, member_count)) in
+        group_secrets.iter().zip(groups)
+    {
+        let members = split_secret(*member_threshold, *member_count, group_secret)?;
+        result.push(
+            members
+                .into_iter()
+                .map(|(member_index, value)| Share {
+                    identifier,
+                    extendable,
+                    iteration_exponent,
+                    group_index: *group_index,
+                    group_threshold,
+                    group_count: groups.len() as u8,
+                    member_index,
+                    member_threshold: *member_threshold,
+                    value,
+                })
+                .collect(),
+        );
+    }
+    Ok(result)
+}
+
+/// 由足够的分片恢复主秘密
+pub fn combine_shares(
+    phrases: &[&str],
+    passphrase: &str,
+) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    let shares = phrases
+        .iter()
+        .map(|p| Share::from_words(p))
+        .collect::<Result<Vec<_>, _>>()?;
+    let first = shares
+        .first()
+        .ok_or_else(|| slip39_error("No shares provided"))?;
+    if shares
+        .iter()
+        .any(|s| s.common_params() != first.common_params())
+    {
+        return Err(slip39_error("Shares do not belong to the same secret"));
+    }
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in &shares {
+        let group = groups.entry(share.group_index).or_default();
+        if let Some(existing) = group.iter().find(|s| s.member_index == share.member_index) {
+            if existing.value != share.value {
+                return Err(slip39_error("Conflicting shares with the same index"));
+            }
+            continue;
+        }
+        if group
+            .first()
+            .is_some_and(|s| s.member_threshold != share.member_threshold)
+        {
+            return Err(slip39_error("Inconsistent member threshold in group"));
+        }
+        group.push(share);
+    }
+
+    let mut group_secrets = Vec::new();
+    for (group_index, members) in &groups {
+        let threshold = members[0].member_threshold;
+        if members.len() < threshold as usize {
+            continue;
+        }
+        let points: Vec<(u8, &[u8])> = members
+            .iter()
+            .take(threshold as usize)
+            .map(|s| (s.member_index, s.value.as_slice()))
+            .collect();
+        group_secrets.push((*group_index, recover_secret(threshold, &points)?));
+        if group_secrets.len() == first.group_threshold as usize {
+            break;
+        }
+    }
+    if group_secrets.len() < first.group_threshold as usize {
+        return Err(slip39_error(format!(
+            "Insufficient shares, {} complete groups required, got {}",
+            first.group_threshold,
+            group_secrets.len()
+        )));
+    }
+
+    let points: Vec<(u8, &[u8])> = group_secrets
+        .iter()
+        .map(|(i, v)| (*i, v.as_slice()))
+        .collect();
+    let encrypted = recover_secret(first.group_threshold, &points)?;
+    feistel(
+        &encrypted,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+        true,
+    )
+}
+
+impl FreeWebMovementAddress {
+    /// 以 BIP39 助记词熵作为主秘密拆分为 SLIP-39 分片，passphrase 用于加密主秘密；
+    /// 分片不含 BIP39 passphrase，使用了 passphrase 的钱包恢复时须另行提供
+    pub fn to_slip39_shares(
+        &self,
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[(u8, u8)],
+    ) -> Result<Vec<Vec<Zeroizing<String>>>, AccountError> {
+        let entropy = Zeroizing::new(self.mnemonic.to_entropy());
+        let shares = generate_shares(
+            &entropy,
+            passphrase,
+            group_threshold,
+            groups,
+            SLIP39_ITERATION_EXPONENT,
+            true,
+        )?;
+        Ok(shares
+            .iter()
+            .map(|group| group.iter().map(Share::to_words).collect())
+            .collect())
+    }
+
+    /// 由分片恢复地址；passphrase 解密主秘密，bip39_passphrase 用于派生地址，
+    /// 分片中不含语言和派生信息，需要调用方传入
+    pub fn from_slip39_shares(
+        phrases: &[&str],
+        passphrase: &str,
+        bip39_passphrase: &str,
+        language: Language,
+        address_info: Option<AddressInfo>,
+    ) -> Result<Self, AccountError> {
+        let entropy = combine_shares(phrases, passphrase)?;
+        let mnemonic = Mnemonic::from_entropy_in(language, &entropy)?;
+        let mnemonic_info = MnemonicInfo {
+            language,
+            word_count: mnemonic.word_count(),
+            phrase: mnemonic.to_string().into(),
+            passphrase: bip39_passphrase.to_string().into(),
+        };
+        FreeWebMovementAddress::try_new(mnemonic_info, address_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slip39_vectors() {
+        // SLIP-39 官方测试向量，passphrase 为 "TREZOR"
+        let single = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard";
+        assert_eq!(
+            hex::encode(combine_shares(&[single], "TREZOR").unwrap().as_slice()),
+            "bb54aac4b89dc868ba37d9cc21b2cece"
+        );
+        let bad_checksum = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney";
+        assert!(combine_shares(&[bad_checksum], "TREZOR").is_err());
+
+        let two_of_three = [
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ];
+        assert_eq!(
+            hex::encode(combine_shares(&two_of_three, "TREZOR").unwrap().as_slice()),
+            "b43ceb7e57a0ea8766221624d01b0864"
+        );
+        assert!(combine_shares(&two_of_three[..1], "TREZOR").is_err());
+
+        let long = "theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck";
+        assert_eq!(
+            hex::encode(combine_shares(&[long], "TREZOR").unwrap().as_slice()),
+            "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92"
+        );
+    }
+
+    #[test]
+    fn test_slip39_address_round_trip() {
+        let address = FreeWebMovementAddress::random();
+        // 两组中任意一组即可：2-of-3 与 3-of-5
+        let groups = address
+            .to_slip39_shares("treasury", 1, &[(2, 3), (3, 5)])
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].len(), 5);
+
+        let picked: Vec<&str> = [&groups[1][4], &groups[1][0], &groups[1][2]]
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        let recovered = FreeWebMovementAddress::from_slip39_shares(
+            &picked,
+            "treasury",
+            "",
+            Language::English,
+            None,
+        )
+        .unwrap();
+        assert_eq!(recovered.mnemonic, address.mnemonic);
+        assert_eq!(recovered.to_string(), address.to_string());
+
+        // 分片不足或 passphrase 错误都无法得到原地址
+        assert!(
+            FreeWebMovementAddress::from_slip39_shares(
+                &picked[..2],
+                "treasury",
+                "",
+                Language::English,
+                None
+            )
+            .is_err()
+        );
+        let wrong = FreeWebMovementAddress::from_slip39_shares(
+            &picked,
+            "wrong",
+            "",
+            Language::English,
+            None,
+        )
+        .unwrap();
+        assert_ne!(wrong.mnemonic, address.mnemonic);
+
+        assert!(address.to_slip39_shares("", 2, &[(1, 3), (2, 3)]).is_err());
+        assert!(address.to_slip39_shares("", 3, &[(2, 3), (2, 3)]).is_err());
+    }
+
+    #[test]
+    fn test_slip39_with_bip39_passphrase() {
+        let mnemonic_info = MnemonicInfo {
+            language: Language::English,
+            word_count: 12,
+            phrase: crate::test_utils::TEST_PHRASE.to_string().into(),
+            passphrase: "correct horse".to_string().into(),
+        };
+        let address = FreeWebMovementAddress::try_new(mnemonic_info, None).unwrap();
+        assert!(address.mnemonic_meta().has_passphrase);
+
+        let groups = address.to_slip39_shares("treasury", 1, &[(1, 1)]).unwrap();
+        let share = groups[0][0].as_str();
+
+        // 分片只还原助记词，地址由单独传入的 BIP39 passphrase 决定
+        let recovered = FreeWebMovementAddress::from_slip39_shares(
+            &[share],
+            "treasury",
+            "correct horse",
+            Language::English,
+            None,
+        )
+        .unwrap();
+        assert_eq!(recovered.mnemonic, address.mnemonic);
+        assert_eq!(recovered.to_string(), address.to_string());
+        assert!(recovered.mnemonic_meta().has_passphrase);
+
+        let without = FreeWebMovementAddress::from_slip39_shares(
+            &[share],
+            "treasury",
+            "",
+            Language::English,
+            None,
+        )
+        .unwrap();
+        assert_eq!(without.mnemonic, address.mnemonic);
+        assert_ne!(without.to_string(), address.to_string());
+    }
+}
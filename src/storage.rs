@@ -0,0 +1,386 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+
+use crate::consts::WALLET_LOCK_RETRY_MS;
+#[cfg(unix)]
+use crate::consts::{WALLET_DIR_MODE, WALLET_FILE_MODE};
+use crate::error::AccountError;
+
+/// 发现权限过宽的钱包文件时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PermissionPolicy {
+    /// 记录警告后继续读取
+    #[default]
+    Warn,
+    /// 拒绝读取，需先修复权限
+    Strict,
+}
+
+/// 递归创建目录，新建的目录权限为 0700；已存在的目录不做修改
+pub fn create_private_dir(dir: &Path) -> Result<(), AccountError> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    builder.mode(WALLET_DIR_MODE);
+    Ok(builder.create(dir)?)
+}
+
+/// 返回组或其他用户可访问的权限位；非 Unix 平台始终为 None
+pub fn insecure_mode(path: &Path) -> Result<Option<u32>, AccountError> {
+    #[cfg(unix)]
+    {
+        let mode = fs::metadata(path)?.permissions().mode() & 0o777;
+        Ok((mode & 0o077 != 0).then_some(mode))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(None)
+    }
+}
+
+/// 读取钱包前检查权限：Warn 只记录警告，Strict 返回错误
+pub fn check_permissions(path: &Path, policy: PermissionPolicy) -> Result<(), AccountError> {
+    let Some(mode) = insecure_mode(path)? else {
+        return Ok(());
+    };
+    let message = format!(
+        "{:?} has mode {:04o} and is accessible by other users, repair the wallet directory permissions",
+        path, mode
+    );
+    match policy {
+        PermissionPolicy::Warn => {
+            tracing::warn!("{}", message);
+            Ok(())
+        }
+        PermissionPolicy::Strict => Err(AccountError::Permissions(message)),
+    }
+}
+
+/// 把目录及其下所有子目录设为 0700、文件设为 0600，返回被修改的路径
+pub fn repair_permissions(dir: &Path) -> Result<Vec<PathBuf>, AccountError> {
+    let mut repaired = Vec::new();
+    #[cfg(unix)]
+    {
+        let metadata = fs::symlink_metadata(dir)?;
+        let mode = if metadata.is_dir() {
+            WALLET_DIR_MODE
+        } else {
+            WALLET_FILE_MODE
+        };
+        if metadata.permissions().mode() & 0o777 != mode {
+            fs::set_permissions(dir, fs::Permissions::from_mode(mode))?;
+            repaired.push(dir.to_path_buf());
+        }
+        if metadata.is_dir() {
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                // 不跟随符号链接，避免修改钱包目录以外的文件
+                if !entry.file_type()?.is_symlink() {
+                    repaired.extend(repair_permissions(&entry.path())?);
+                }
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(repaired)
+}
+
+/// 同一进程内临时文件名的序号
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 同步目录项，保证 rename 在断电后仍然生效；非 Unix 平台不支持打开目录，直接跳过
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        File::open(dir)?.sync_all()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+        Ok(())
+    }
+}
+
+/// 原子写入：先写入同目录下的临时文件（权限 0600）并 fsync，再 rename 覆盖目标文件并 fsync 目录；
+/// 任一步骤失败都会删除临时文件，原文件保持不变
+pub fn atomic_write(path: &Path, contents: &[u8]) -> Result<(), AccountError> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a file path: {:?}", path),
+        )
+    })?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let temp = dir.join(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        // 临时文件创建时即为 0600，rename 后保持不变
+        #[cfg(unix)]
+        options.mode(WALLET_FILE_MODE);
+        let mut file = options.open(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp, path)?;
+        sync_dir(dir)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    Ok(result?)
+}
+
+/// 钱包锁的类型：读取时共享，修改时独占
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    Shared,
+    Exclusive,
+}
+
+/// 钱包文件对应的锁文件 `.<文件名>.lock`；钱包文件会被原子替换，不能直接锁定钱包文件本身
+pub fn lock_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.lock", file_name))
+}
+
+/// 进程是否仍在运行；无法判断的平台一律视为仍在运行
+fn process_alive(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        Path::new(&format!("/proc/{}", pid)).exists()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        true
+    }
+}
+
+/// 锁文件中记录的独占锁持有者 PID
+fn lock_holder(file: &mut File) -> Option<u32> {
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
+/// 钱包文件的咨询锁，持有期间其他进程无法取得冲突的锁；drop 时释放。
+/// 独占锁持有者把 PID 写入锁文件，供等待方报告；进程崩溃后系统自动释放锁，残留的 PID 在下次加锁时清除
+#[derive(Debug)]
+pub struct WalletLock {
+    file: Option<File>,
+    mode: LockMode,
+}
+
+impl WalletLock {
+    /// 在 timeout 内反复尝试加锁，超时返回 WalletLocked 错误
+    pub fn acquire(path: &Path, mode: LockMode, timeout: Duration) -> Result<Self, AccountError> {
+        let lock_file = lock_path(path);
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create(true).truncate(false);
+        #[cfg(unix)]
+        options.mode(WALLET_FILE_MODE);
+        let mut file = match options.open(&lock_file) {
+            Ok(file) => file,
+            // 只读目录中无法创建锁文件，此时也不可能有其他进程写入，读取时不加锁
+            Err(e)
+                if mode == LockMode::Shared
+                    && matches!(
+                        e.kind(),
+                        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem
+                    ) =>
+            {
+                tracing::warn!(
+                    "cannot create lock file {:?}: {}, reading unlocked",
+                    lock_file,
+                    e
+                );
+                return Ok(Self { file: None, mode });
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let result = match mode {
+                LockMode::Shared => file.try_lock_shared(),
+                LockMode::Exclusive => file.try_lock(),
+            };
+            match result {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+            if Instant::now() >= deadline {
+                let holder = match lock_holder(&mut file) {
+                    Some(pid) if process_alive(pid) => format!("PID {}", pid),
+                    Some(pid) => format!("another process (recorded PID {} has exited)", pid),
+                    None => "another process".to_string(),
+                };
+                return Err(AccountError::WalletLocked(format!(
+                    "{:?} is in use by {}, gave up after {:?}",
+                    path, holder, timeout
+                )));
+            }
+            thread::sleep(Duration::from_millis(WALLET_LOCK_RETRY_MS));
+        }
+
+        // 取得锁后仍有 PID 说明上一个独占持有者未正常释放（已崩溃），清除失效记录
+        if let Some(pid) = lock_holder(&mut file) {
+            tracing::warn!("removing stale lock {:?} left by PID {}", lock_file, pid);
+            file.set_len(0)?;
+        }
+        if mode == LockMode::Exclusive {
+            file.seek(SeekFrom::Start(0))?;
+            write!(file, "{}", std::process::id())?;
+            file.flush()?;
+        }
+        Ok(Self {
+            file: Some(file),
+            mode,
+        })
+    }
+
+    pub fn mode(&self) -> LockMode {
+        self.mode
+    }
+}
+
+impl Drop for WalletLock {
+    fn drop(&mut self) {
+        if let Some(file) = &self.file {
+            if self.mode == LockMode::Exclusive {
+                let _ = file.set_len(0);
+            }
+            let _ = file.unlock();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_write() {
+        let tmp_dir = "/tmp/test_atomic_write";
+        let _ = fs::remove_dir_all(tmp_dir);
+        fs::create_dir_all(tmp_dir).unwrap();
+        let path = Path::new(tmp_dir).join("wallet.json");
+
+        atomic_write(&path, b"first").unwrap();
+        atomic_write(&path, b"second").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+
+        // 目标无法被替换时报错，原内容保留且不留下临时文件
+        let blocked = Path::new(tmp_dir).join("blocked");
+        fs::create_dir_all(blocked.join("child")).unwrap();
+        assert!(atomic_write(&blocked, b"data").is_err());
+        assert!(blocked.join("child").is_dir());
+        assert!(atomic_write(Path::new("/tmp/test_atomic_write/missing/x"), b"data").is_err());
+
+        let entries: Vec<String> = fs::read_dir(tmp_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert!(entries.iter().all(|name| !name.ends_with(".tmp")));
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_permissions() {
+        let tmp_dir = Path::new("/tmp/test_private_permissions");
+        let _ = fs::remove_dir_all(tmp_dir);
+        let wallet_dir = tmp_dir.join("wallets");
+        create_private_dir(&wallet_dir).unwrap();
+        let mode = |p: &Path| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&wallet_dir), WALLET_DIR_MODE);
+
+        let path = wallet_dir.join("wallet.json");
+        atomic_write(&path, b"{}").unwrap();
+        assert_eq!(mode(&path), WALLET_FILE_MODE);
+        assert!(check_permissions(&path, PermissionPolicy::Strict).is_ok());
+
+        // 权限过宽：Warn 继续，Strict 拒绝，修复后通过
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        fs::set_permissions(&wallet_dir, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(insecure_mode(&path).unwrap(), Some(0o644));
+        assert!(check_permissions(&path, PermissionPolicy::Warn).is_ok());
+        assert!(matches!(
+            check_permissions(&path, PermissionPolicy::Strict),
+            Err(AccountError::Permissions(_))
+        ));
+        let repaired = repair_permissions(&wallet_dir).unwrap();
+        assert_eq!(repaired, vec![wallet_dir.clone(), path.clone()]);
+        assert_eq!(mode(&path), WALLET_FILE_MODE);
+        assert_eq!(mode(&wallet_dir), WALLET_DIR_MODE);
+        assert!(repair_permissions(&wallet_dir).unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_lock() {
+        let tmp_dir = "/tmp/test_wallet_lock";
+        let _ = fs::remove_dir_all(tmp_dir);
+        fs::create_dir_all(tmp_dir).unwrap();
+        let path = Path::new(tmp_dir).join("wallet.json");
+        let lock_file = lock_path(&path);
+        assert_eq!(lock_file, Path::new(tmp_dir).join(".wallet.json.lock"));
+        let timeout = Duration::from_millis(200);
+
+        // 共享锁可以并存，但阻止独占锁
+        let first = WalletLock::acquire(&path, LockMode::Shared, timeout).unwrap();
+        let second = WalletLock::acquire(&path, LockMode::Shared, timeout).unwrap();
+        let err = WalletLock::acquire(&path, LockMode::Exclusive, timeout).unwrap_err();
+        assert!(err.to_string().contains("in use by another process"));
+        drop((first, second));
+
+        // 独占锁记录 PID，等待方报告持有者
+        let exclusive = WalletLock::acquire(&path, LockMode::Exclusive, timeout).unwrap();
+        assert_eq!(
+            fs::read_to_string(&lock_file).unwrap(),
+            std::process::id().to_string()
+        );
+        let err = WalletLock::acquire(&path, LockMode::Shared, timeout).unwrap_err();
+        assert!(matches!(err, AccountError::WalletLocked(_)));
+        assert!(
+            err.to_string()
+                .contains(&format!("in use by PID {}", std::process::id()))
+        );
+        drop(exclusive);
+        assert!(fs::read_to_string(&lock_file).unwrap().is_empty());
+
+        // 崩溃进程残留的 PID 不影响加锁，并被清除
+        fs::write(&lock_file, "4194305").unwrap();
+        let shared = WalletLock::acquire(&path, LockMode::Shared, timeout).unwrap();
+        assert_eq!(shared.mode(), LockMode::Shared);
+        assert!(fs::read_to_string(&lock_file).unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+}
@@ -0,0 +1,24 @@
+//! 单元测试共用的固定地址
+
+use bip39::Language;
+use bitcoin::{AddressType, Network};
+
+use crate::address::{AddressInfo, FreeWebMovementAddress, MnemonicInfo};
+
+/// BIP39 官方测试向量
+pub(crate) const TEST_PHRASE: &str =
+    "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+/// 由 TEST_PHRASE 派生的固定地址，结果可复现
+pub(crate) fn fixture_address(
+    network: Network,
+    address_type: AddressType,
+) -> FreeWebMovementAddress {
+    let mnemonic_info = MnemonicInfo {
+        language: Language::English,
+        word_count: 12,
+        phrase: TEST_PHRASE.to_string().into(),
+        passphrase: String::new().into(),
+    };
+    FreeWebMovementAddress::new(mnemonic_info, Some(AddressInfo::new(network, address_type)))
+}
@@ -0,0 +1,379 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bip39::Language;
+use bitcoin::{AddressType, Network};
+use regex::Regex;
+
+use crate::address::{AddressInfo, FreeWebMovementAddress, MnemonicInfo};
+use crate::consts::{
+    BASE58_ADDRESS_LENGTH, BASE58_CHARSET, BECH32_CHARSET, MNEMONIC_WORD_COUNT,
+    VANITY_PROGRESS_INTERVAL_MS,
+};
+use crate::error::AccountError;
+
+/// 靓号模式，匹配 `FWMC:Zz:` 之后的地址部分
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VanityPattern {
+    Prefix(String),
+    Suffix(String),
+    Regex(String),
+}
+
+/// 地址的固定开头、字符集与长度
+struct AddressLayout {
+    leads: Vec<String>,
+    charset: &'static str,
+    length: usize,
+}
+
+impl AddressLayout {
+    fn new(network: Network, address_type: AddressType) -> Result<Self, AccountError> {
+        let hrp = match network {
+            Network::Bitcoin => "bc",
+            Network::Testnet | Network::Signet => "tb",
+            Network::Regtest => "bcrt",
+            other => {
+                return Err(AccountError::Vanity(format!(
+                    "unsupported network {}",
+                    other
+                )));
+            }
+        };
+        let mainnet = network == Network::Bitcoin;
+        let base58 = |leads: &[&str]| AddressLayout {
+            leads: leads.iter().map(|l| l.to_string()).collect(),
+            charset: BASE58_CHARSET,
+            length: BASE58_ADDRESS_LENGTH,
+        };
+        // bech32：hrp + "1" + 见证版本 + 数据 + 6 位校验和
+        let bech32 = |version: char, data: usize| AddressLayout {
+            leads: vec![format!("{}1{}", hrp, version)],
+            charset: BECH32_CHARSET,
+            length: hrp.len() + 2 + data + 6,
+        };
+        Ok(match address_type {
+            AddressType::P2pkh if mainnet => base58(&["1"]),
+            AddressType::P2pkh => base58(&["m", "n"]),
+            AddressType::P2sh if mainnet => base58(&["3"]),
+            AddressType::P2sh => base58(&["2"]),
+            AddressType::P2wpkh => bech32('q', 32),
+            AddressType::P2tr => bech32('p', 52),
+            other => return Err(AccountError::UnsupportedAddressType(other.to_string())),
+        })
+    }
+
+    fn check_charset(&self, chars: &str) -> Result<(), AccountError> {
+        match chars.chars().find(|c| !self.charset.contains(*c)) {
+            Some(c) => Err(AccountError::Vanity(format!(
+                "character '{}' never appears in these addresses, allowed: {}",
+                c, self.charset
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// 校验模式并粗略估计期望尝试次数；base58 首位之后的分布并不完全均匀
+    fn expected_attempts(&self, pattern: &VanityPattern) -> Result<Option<f64>, AccountError> {
+        let base = self.charset.len() as f64;
+        match pattern {
+            VanityPattern::Prefix(prefix) => {
+                if prefix.len() > self.length {
+                    return Err(AccountError::Vanity(format!(
+                        "prefix is longer than the {} character address",
+                        self.length
+                    )));
+                }
+                let matching: Vec<&String> = self
+                    .leads
+                    .iter()
+                    .filter(|l| l.starts_with(prefix.as_str()) || prefix.starts_with(l.as_str()))
+                    .collect();
+                let Some(lead) = matching.first() else {
+                    return Err(AccountError::Vanity(format!(
+                        "addresses start with {}",
+                        self.leads.join(" or ")
+                    )));
+                };
+                let rest = prefix.get(lead.len()..).unwrap_or("");
+                self.check_charset(rest)?;
+                let leads = self.leads.len() as f64 / matching.len() as f64;
+                Ok(Some(leads * base.powi(rest.chars().count() as i32)))
+            }
+            VanityPattern::Suffix(suffix) => {
+                let max = self.length - self.leads[0].len();
+                if suffix.len() > max {
+                    return Err(AccountError::Vanity(format!(
+                        "suffix is longer than the {} free characters",
+                        max
+                    )));
+                }
+                self.check_charset(suffix)?;
+                Ok(Some(base.powi(suffix.chars().count() as i32)))
+            }
+            VanityPattern::Regex(_) => Ok(None),
+        }
+    }
+}
+
+/// 取消句柄，可在其他线程中停止搜索
+#[derive(Debug, Clone, Default)]
+pub struct VanityCancel(Arc<AtomicBool>);
+
+impl VanityCancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// 搜索进度
+#[derive(Debug, Clone, Copy)]
+pub struct VanityProgress {
+    pub attempts: u64,
+    pub elapsed: Duration,
+    /// 每秒尝试次数
+    pub rate: f64,
+    pub expected_attempts: Option<f64>,
+    /// 搜索无记忆，剩余期望时间始终为 expected_attempts / rate
+    pub eta: Option<Duration>,
+}
+
+/// 多线程靓号搜索，每次尝试生成新的助记词，找到的地址可直接作为钱包保存
+pub struct VanitySearch {
+    pattern: VanityPattern,
+    regex: Option<Regex>,
+    info: AddressInfo,
+    language: Language,
+    word_count: usize,
+    threads: usize,
+    expected_attempts: Option<f64>,
+    cancel: VanityCancel,
+}
+
+impl VanitySearch {
+    /// 校验模式：bech32 不区分大小写，统一转为小写；永远无法匹配的模式直接报错
+    pub fn new(pattern: VanityPattern, info: AddressInfo) -> Result<Self, AccountError> {
+        let layout = AddressLayout::new(info.network, info.address_type)?;
+        let pattern = match pattern {
+            VanityPattern::Prefix(p) if layout.charset == BECH32_CHARSET => {
+                VanityPattern::Prefix(p.to_lowercase())
+            }
+            VanityPattern::Suffix(s) if layout.charset == BECH32_CHARSET => {
+                VanityPattern::Suffix(s.to_lowercase())
+            }
+            other => other,
+        };
+        if matches!(&pattern, VanityPattern::Prefix(p) | VanityPattern::Suffix(p) if p.is_empty()) {
+            return Err(AccountError::Vanity("pattern is empty".to_string()));
+        }
+        let regex = match &pattern {
+            VanityPattern::Regex(r) => {
+                Some(Regex::new(r).map_err(|e| AccountError::Vanity(e.to_string()))?)
+            }
+            _ => None,
+        };
+        let expected_attempts = layout.expected_attempts(&pattern)?;
+        Ok(VanitySearch {
+            pattern,
+            regex,
+            info,
+            language: Language::English,
+            word_count: MNEMONIC_WORD_COUNT,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            expected_attempts,
+            cancel: VanityCancel::default(),
+        })
+    }
+
+    /// 工作线程数，默认使用全部 CPU 核心
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn mnemonic(mut self, language: Language, word_count: usize) -> Self {
+        self.language = language;
+        self.word_count = word_count;
+        self
+    }
+
+    pub fn cancel_handle(&self) -> VanityCancel {
+        self.cancel.clone()
+    }
+
+    pub fn expected_attempts(&self) -> Option<f64> {
+        self.expected_attempts
+    }
+
+    /// 匹配不含 `FWMC:Zz:` 前缀的地址
+    pub fn is_match(&self, address: &str) -> bool {
+        match (&self.pattern, &self.regex) {
+            (_, Some(regex)) => regex.is_match(address),
+            (VanityPattern::Prefix(prefix), _) => address.starts_with(prefix.as_str()),
+            (VanityPattern::Suffix(suffix), _) => address.ends_with(suffix.as_str()),
+            _ => false,
+        }
+    }
+
+    fn progress(&self, attempts: u64, elapsed: Duration) -> VanityProgress {
+        let rate = attempts as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        let eta = match self.expected_attempts {
+            // 长模式的预计时间可能超出 Duration 的范围，此时不给出 eta
+            Some(expected) if rate > 0.0 => Duration::try_from_secs_f64(expected / rate).ok(),
+            _ => None,
+        };
+        VanityProgress {
+            attempts,
+            elapsed,
+            rate,
+            expected_attempts: self.expected_attempts,
+            eta,
+        }
+    }
+
+    /// 搜索直到匹配，期间每秒回调一次进度；被取消时返回 None
+    pub fn run<F: FnMut(&VanityProgress)>(
+        &self,
+        mut on_progress: F,
+    ) -> Result<Option<FreeWebMovementAddress>, AccountError> {
+        let started = Instant::now();
+        let attempts = AtomicU64::new(0);
+        let found = AtomicBool::new(false);
+        let (tx, rx) = mpsc::channel();
+        let interval = Duration::from_millis(VANITY_PROGRESS_INTERVAL_MS);
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                let tx = tx.clone();
+                let (attempts, found) = (&attempts, &found);
+                scope.spawn(move || {
+                    while !found.load(Ordering::Relaxed) && !self.cancel.is_cancelled() {
+                        let mnemonic_info = MnemonicInfo {
+                            language: self.language,
+                            word_count: self.word_count,
+                            phrase: String::new().into(),
+                            passphrase: String::new().into(),
+                        };
+                        let result =
+                            FreeWebMovementAddress::try_new(mnemonic_info, Some(self.info.clone()));
+                        attempts.fetch_add(1, Ordering::Relaxed);
+                        match result {
+                            Ok(address) if !self.is_match(&address.address.to_string()) => {}
+                            other => {
+                                found.store(true, Ordering::Relaxed);
+                                let _ = tx.send(other);
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+            drop(tx);
+
+            // 所有线程都退出且没有结果，说明搜索已被取消
+            let result = loop {
+                match rx.recv_timeout(interval) {
+                    Ok(result) => break result.map(Some),
+                    Err(RecvTimeoutError::Timeout) => on_progress(
+                        &self.progress(attempts.load(Ordering::Relaxed), started.elapsed()),
+                    ),
+                    Err(RecvTimeoutError::Disconnected) => break Ok(None),
+                }
+            };
+            found.store(true, Ordering::Relaxed);
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vanity_search() {
+        let info = AddressInfo::new(Network::Regtest, AddressType::P2wpkh);
+        let search = VanitySearch::new(VanityPattern::Prefix("BCRT1QQ".to_string()), info)
+            .unwrap()
+            .threads(2);
+        assert_eq!(search.expected_attempts(), Some(32.0));
+        let address = search.run(|_| {}).unwrap().unwrap();
+        assert!(address.address.to_string().starts_with("bcrt1qq"));
+        assert_eq!(address.mnemonic.word_count(), MNEMONIC_WORD_COUNT);
+
+        let info = AddressInfo::new(Network::Testnet, AddressType::P2pkh);
+        let search = VanitySearch::new(VanityPattern::Regex("[a-k]$".to_string()), info).unwrap();
+        let address = search.run(|_| {}).unwrap().unwrap();
+        assert!(search.is_match(&address.address.to_string()));
+
+        // 取消后返回 None
+        let info = AddressInfo::new(Network::Bitcoin, AddressType::P2tr);
+        let search = VanitySearch::new(VanityPattern::Suffix("qqqqqqqqqq".to_string()), info)
+            .unwrap()
+            .threads(1);
+        search.cancel_handle().cancel();
+        assert!(search.run(|_| {}).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_progress_eta() {
+        let info = AddressInfo::new(Network::Bitcoin, AddressType::P2pkh);
+        let search =
+            VanitySearch::new(VanityPattern::Prefix("1zz".to_string()), info.clone()).unwrap();
+        let progress = search.progress(10_000, Duration::from_secs(1));
+        assert_eq!(
+            progress.eta,
+            Some(Duration::from_secs_f64(58.0 * 58.0 / 10_000.0))
+        );
+
+        // 58^14 次尝试远超 Duration 上限，不能 panic
+        let long = VanityPattern::Prefix(format!("1{}", "z".repeat(14)));
+        let search = VanitySearch::new(long, info).unwrap();
+        assert!(search.expected_attempts().unwrap() > 4e24);
+        let progress = search.progress(10_000, Duration::from_secs(1));
+        assert_eq!(progress.eta, None);
+        assert_eq!(progress.attempts, 10_000);
+    }
+
+    #[test]
+    fn test_impossible_patterns() {
+        let new = |pattern: VanityPattern, network, address_type| {
+            VanitySearch::new(pattern, AddressInfo::new(network, address_type))
+        };
+        let prefix = |p: &str| VanityPattern::Prefix(p.to_string());
+
+        assert!(new(prefix("1Zz"), Network::Bitcoin, AddressType::P2pkh).is_ok());
+        assert!(new(prefix("n"), Network::Testnet, AddressType::P2pkh).is_ok());
+        assert!(new(prefix("3"), Network::Bitcoin, AddressType::P2pkh).is_err());
+        assert!(new(prefix("10"), Network::Bitcoin, AddressType::P2pkh).is_err());
+        assert!(new(prefix("1l"), Network::Bitcoin, AddressType::P2sh).is_err());
+        assert!(new(prefix("bc1qb"), Network::Bitcoin, AddressType::P2wpkh).is_err());
+        assert!(new(prefix("bc1q"), Network::Bitcoin, AddressType::P2tr).is_err());
+        assert!(new(prefix("tb1p"), Network::Testnet, AddressType::P2tr).is_ok());
+        assert!(new(prefix(""), Network::Bitcoin, AddressType::P2pkh).is_err());
+        assert!(
+            new(
+                VanityPattern::Suffix("q".repeat(40)),
+                Network::Bitcoin,
+                AddressType::P2wpkh
+            )
+            .is_err()
+        );
+        assert!(
+            new(
+                VanityPattern::Regex("(".to_string()),
+                Network::Bitcoin,
+                AddressType::P2pkh
+            )
+            .is_err()
+        );
+        assert!(new(prefix("bc1q"), Network::Bitcoin, AddressType::P2wsh).is_err());
+    }
+}
@@ -1,82 +1,711 @@
 use std::{
-    io::{Read, Write},
-    path::PathBuf,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
+use bitcoin::AddressType;
+use bitcoin::bip32::Xpub;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
 use crate::{
+    account::{AccountIndexes, Chain, HdAccount},
     address::FreeWebMovementAddress,
-    consts::{DEFAULT_WALLET_DIR, DEFAULT_WALLET_FILE},
+    backup::{BackupRetention, backup_file_path, list_backups, prune_backups},
+    consts::{
+        BACKUP_PREFIX, DEFAULT_ACCOUNT_LABEL, DEFAULT_WALLET_DIR, DEFAULT_WALLET_FILE,
+        SNAPSHOT_PREFIX, WALLET_LOCK_TIMEOUT_MS, WALLET_SCHEMA_VERSION,
+    },
+    error::AccountError,
+    keystore::Keystore,
+    storage::{
+        LockMode, PermissionPolicy, WalletLock, atomic_write, check_permissions,
+        create_private_dir, repair_permissions,
+    },
 };
 
+/// 多账户钱包
+///
+/// 0.2.0 起 `address`、`hd` 不再是公开字段（破坏性变更），改用 [`Wallet::address`]、
+/// [`Wallet::default_account`] 访问默认账户；旧字段的可变访问暂由已弃用的
+/// [`Wallet::address_mut`]、[`Wallet::hd_mut`] 提供
 pub struct Wallet {
-    pub address: FreeWebMovementAddress,
+    accounts: Vec<WalletAccount>,
+    /// 默认账户在 accounts 中的下标，构造和修改账户时保证有效
+    default_account: usize,
     pub directory: String,
     pub filename: String,
+    password: Option<Zeroizing<String>>,
+    permission_policy: PermissionPolicy,
+    lock_timeout: Duration,
+    retention: Option<BackupRetention>,
+    auto_backup: bool,
 }
 
-impl Wallet {
-    pub fn new(directory: Option<&str>, filename: Option<&str>) -> Self {
-        // 获取用户 AppData 目录（Windows / macOS / Linux）
-        let mut dir = dirs::data_dir().unwrap_or_else(|| {
-            tracing::warn!(
-                "Cannot determine app data directory, falling back to current directory"
-            );
-            PathBuf::from(".")
-        });
-
-        match directory {
-            Some(dir_str) => {
-                if dir_str.starts_with('/') {
-                    // 如果是绝对路径，则直接使用
-                    dir = PathBuf::from(dir_str);
-                } else {
-                    // 如果是相对路径，则拼接到 AppData 目录
-                    dir.push(dir_str);
-                }
-            }
-            None => {
-                // 如果没有提供目录，则使用默认目录
-                dir.push(DEFAULT_WALLET_DIR);
+/// 钱包中的一个命名账户，拥有独立的助记词，或由其他账户的助记词按不同账户索引派生
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletAccount {
+    pub label: String,
+    /// 创建时间，Unix 时间戳（秒）
+    pub created_at: i64,
+    /// 地址、AddressInfo 与助记词
+    pub address: FreeWebMovementAddress,
+    #[serde(default)]
+    pub hd: AccountIndexes,
+}
+
+impl WalletAccount {
+    pub fn new(label: &str, address: FreeWebMovementAddress, hd: AccountIndexes) -> Self {
+        WalletAccount {
+            label: label.to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            address,
+            hd,
+        }
+    }
+}
+
+/// 钱包文件内容：格式版本、全部账户及默认账户标签
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletFile {
+    pub version: u32,
+    pub accounts: Vec<WalletAccount>,
+    pub default_account: String,
+}
+
+impl WalletFile {
+    pub fn single(account: WalletAccount) -> Self {
+        WalletFile {
+            version: WALLET_SCHEMA_VERSION,
+            default_account: account.label.clone(),
+            accounts: vec![account],
+        }
+    }
+
+    /// 账户非空、标签唯一且默认账户存在
+    pub fn validate(&self) -> Result<(), AccountError> {
+        if self.accounts.is_empty() {
+            return Err(account_error("wallet has no accounts"));
+        }
+        for (i, account) in self.accounts.iter().enumerate() {
+            if self.accounts[..i].iter().any(|a| a.label == account.label) {
+                return Err(account_error(format!(
+                    "duplicate label \"{}\"",
+                    account.label
+                )));
             }
         }
+        if !self
+            .accounts
+            .iter()
+            .any(|a| a.label == self.default_account)
+        {
+            return Err(account_error(format!(
+                "default account \"{}\" does not exist",
+                self.default_account
+            )));
+        }
+        Ok(())
+    }
+
+    /// 拆分为账户列表和默认账户的下标
+    fn into_accounts(self) -> Result<(Vec<WalletAccount>, usize), AccountError> {
+        let index = self
+            .accounts
+            .iter()
+            .position(|a| a.label == self.default_account)
+            .ok_or_else(|| {
+                account_error(format!(
+                    "default account \"{}\" does not exist",
+                    self.default_account
+                ))
+            })?;
+        Ok((self.accounts, index))
+    }
+
+    /// 取出默认账户的地址，供只处理单个地址的调用方（如 JNI）使用
+    pub fn into_default_address(mut self) -> FreeWebMovementAddress {
+        let index = self
+            .accounts
+            .iter()
+            .position(|a| a.label == self.default_account)
+            .unwrap_or(0);
+        self.accounts.swap_remove(index).address
+    }
+}
 
-        // 检查目录是否存在，不存在则创建
-        if !dir.exists() {
-            std::fs::create_dir_all(&dir).expect("Failed to create wallet directory");
+fn account_error<E: ToString>(e: E) -> AccountError {
+    AccountError::WalletAccount(e.to_string())
+}
+
+fn schema_error<E: ToString>(e: E) -> AccountError {
+    AccountError::WalletSchema(e.to_string())
+}
+
+/// 识别文件版本：有 version 字段直接使用，否则按布局判断
+fn detect_schema_version(value: &serde_json::Value) -> Result<u32, AccountError> {
+    match value.get("version") {
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| schema_error(format!("invalid version field: {}", version))),
+        None if value.get("accounts").is_some() => Ok(1),
+        None => Ok(0),
+    }
+}
+
+/// 版本 0 → 1：旧的单地址文件（CLI 与 JNI 写入）转换为一个默认账户，没有创建时间，记为迁移时间
+fn migrate_v0(mut value: serde_json::Value) -> Result<serde_json::Value, AccountError> {
+    let hd = value
+        .as_object_mut()
+        .and_then(|object| object.remove("hd"))
+        .unwrap_or_else(|| serde_json::json!(AccountIndexes::default()));
+    Ok(serde_json::json!({
+        "accounts": [{
+            "label": DEFAULT_ACCOUNT_LABEL,
+            "created_at": chrono::Utc::now().timestamp(),
+            "address": value,
+            "hd": hd,
+        }],
+        "default_account": DEFAULT_ACCOUNT_LABEL,
+    }))
+}
+
+/// 版本 1 → 2：布局不变，只补充 version 字段（由 parse_wallet_json 在每步迁移后写入）
+fn migrate_v1(value: serde_json::Value) -> Result<serde_json::Value, AccountError> {
+    Ok(value)
+}
+
+/// 解析钱包 JSON 并逐级升级到当前版本，返回内容及文件原来的版本
+fn parse_wallet_json(json: &[u8]) -> Result<(WalletFile, u32), AccountError> {
+    let mut value: serde_json::Value = serde_json::from_slice(json)?;
+    let original = detect_schema_version(&value)?;
+    if original > WALLET_SCHEMA_VERSION {
+        return Err(schema_error(format!(
+            "version {} is newer than the supported version {}, please upgrade zz-account",
+            original, WALLET_SCHEMA_VERSION
+        )));
+    }
+    let migrations: [fn(serde_json::Value) -> Result<serde_json::Value, AccountError>; 2] =
+        [migrate_v0, migrate_v1];
+    for (from, migrate) in (original..).zip(&migrations[original as usize..]) {
+        value = migrate(value)?;
+        value
+            .as_object_mut()
+            .ok_or_else(|| schema_error("wallet file is not a JSON object"))?
+            .insert("version".to_string(), serde_json::json!(from + 1));
+    }
+    let data: WalletFile = serde_json::from_value(value)?;
+    data.validate()?;
+    Ok((data, original))
+}
+
+/// 解析钱包文件内容并返回原版本；加密文件需要口令，明文文件直接读取（便于迁移旧钱包）
+pub fn decode_wallet_versioned(
+    contents: &str,
+    password: Option<&str>,
+) -> Result<(WalletFile, u32), AccountError> {
+    if !Keystore::is_keystore(contents) {
+        return parse_wallet_json(contents.as_bytes());
+    }
+    let password = password.ok_or_else(|| {
+        AccountError::Keystore("Wallet file is encrypted, password required".to_string())
+    })?;
+    let plaintext = Keystore::from_json(contents)?.decrypt(password)?;
+    parse_wallet_json(&plaintext)
+}
+
+/// 解析钱包文件内容，旧版本在内存中升级到当前版本
+pub fn decode_wallet(contents: &str, password: Option<&str>) -> Result<WalletFile, AccountError> {
+    Ok(decode_wallet_versioned(contents, password)?.0)
+}
+
+/// 序列化钱包内容：有口令时写入加密格式，否则为明文 JSON；钱包与备份文件统一使用缩进格式
+pub fn encode_wallet(
+    wallet_file: &WalletFile,
+    password: Option<&str>,
+) -> Result<String, AccountError> {
+    let json = Zeroizing::new(serde_json::to_string_pretty(wallet_file)?);
+    match password {
+        Some(password) => Keystore::encrypt(json.as_bytes(), password)?.to_json(),
+        None => Ok(json.to_string()),
+    }
+}
+
+/// 写入钱包或备份文件，崩溃或磁盘写满时保留原文件
+pub fn write_wallet_file(path: &Path, contents: &str) -> Result<(), AccountError> {
+    atomic_write(path, contents.as_bytes())
+}
+
+fn read_contents(path: &Path) -> Result<String, AccountError> {
+    let mut contents = String::new();
+    std::fs::File::open(path)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+/// 读取钱包文件，先按 policy 检查权限，读取时持有共享锁；
+/// 旧版本文件改为持有独占锁在原位置升级，升级前把原文件复制为 `<文件名>.v<旧版本>.bak`
+pub fn read_wallet_file(
+    path: &Path,
+    password: Option<&str>,
+    policy: PermissionPolicy,
+    lock_timeout: Duration,
+) -> Result<WalletFile, AccountError> {
+    check_permissions(path, policy)?;
+    let (data, version) = {
+        let _lock = WalletLock::acquire(path, LockMode::Shared, lock_timeout)?;
+        decode_wallet_versioned(&read_contents(path)?, password)?
+    };
+    if version < WALLET_SCHEMA_VERSION {
+        // 释放共享锁后其他进程可能已完成升级，加独占锁后重新读取
+        let _lock = WalletLock::acquire(path, LockMode::Exclusive, lock_timeout)?;
+        let contents = read_contents(path)?;
+        let (data, version) = decode_wallet_versioned(&contents, password)?;
+        if version >= WALLET_SCHEMA_VERSION {
+            return Ok(data);
         }
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(format!(".v{}.bak", version));
+        atomic_write(Path::new(&backup), contents.as_bytes())?;
+        write_wallet_file(path, &encode_wallet(&data, password)?)?;
+        tracing::info!(
+            "migrated wallet {:?} from version {} to {}, original kept at {:?}",
+            path,
+            version,
+            WALLET_SCHEMA_VERSION,
+            backup
+        );
+        return Ok(data);
+    }
+    Ok(data)
+}
 
-        let filename = filename.unwrap_or(DEFAULT_WALLET_FILE).to_string();
-        let mut wallet_file = dir.clone();
-        wallet_file.push(&filename);
+/// 解析钱包目录：绝对路径直接使用，相对路径拼接到 AppData 目录，None 使用默认目录
+pub fn resolve_wallet_dir(directory: Option<&str>) -> PathBuf {
+    // 获取用户 AppData 目录（Windows / macOS / Linux）
+    let mut dir = dirs::data_dir().unwrap_or_else(|| {
+        tracing::warn!("Cannot determine app data directory, falling back to current directory");
+        PathBuf::from(".")
+    });
 
-        // 确保文件的父目录存在
-        if let Some(parent) = wallet_file.parent() {
-            std::fs::create_dir_all(parent).expect("Failed to create wallet parent directory");
+    match directory {
+        Some(dir_str) => {
+            if dir_str.starts_with('/') {
+                // 如果是绝对路径，则直接使用
+                dir = PathBuf::from(dir_str);
+            } else {
+                // 如果是相对路径，则拼接到 AppData 目录
+                dir.push(dir_str);
+            }
+        }
+        None => {
+            // 如果没有提供目录，则使用默认目录
+            dir.push(DEFAULT_WALLET_DIR);
         }
+    }
+
+    dir
+}
+
+/// 解析并创建钱包目录，返回目录、文件名与完整路径
+fn prepare_wallet_path(
+    directory: Option<&str>,
+    filename: Option<&str>,
+) -> Result<(PathBuf, String, PathBuf), AccountError> {
+    let dir = resolve_wallet_dir(directory);
+
+    // 检查目录是否存在，不存在则创建（权限 0700）
+    if !dir.exists() {
+        create_private_dir(&dir)?;
+    }
+
+    let filename = filename.unwrap_or(DEFAULT_WALLET_FILE).to_string();
+    let mut wallet_file = dir.clone();
+    wallet_file.push(&filename);
+
+    // 确保文件的父目录存在
+    if let Some(parent) = wallet_file.parent() {
+        create_private_dir(parent)?;
+    }
+
+    Ok((dir, filename, wallet_file))
+}
+
+impl Wallet {
+    pub fn new(directory: Option<&str>, filename: Option<&str>) -> Self {
+        Wallet::try_new(directory, filename).expect("Failed to open wallet")
+    }
+
+    pub fn try_new(directory: Option<&str>, filename: Option<&str>) -> Result<Self, AccountError> {
+        Wallet::open(directory, filename, None)
+    }
 
-        // 如果文件存在则读取，否则新建随机地址
-        let address = if wallet_file.exists() {
-            let mut file = std::fs::File::open(&wallet_file).expect("Failed to open wallet file");
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)
-                .expect("Failed to read wallet file");
+    /// 打开钱包；给定 password 时新建的钱包会加密保存，已加密的钱包会被解密
+    pub fn open(
+        directory: Option<&str>,
+        filename: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Self, AccountError> {
+        Wallet::open_with_policy(directory, filename, password, PermissionPolicy::default())
+    }
+
+    /// 打开钱包，并按 policy 处理权限过宽的钱包与备份文件
+    pub fn open_with_policy(
+        directory: Option<&str>,
+        filename: Option<&str>,
+        password: Option<&str>,
+        permission_policy: PermissionPolicy,
+    ) -> Result<Self, AccountError> {
+        let (dir, filename, wallet_file) = prepare_wallet_path(directory, filename)?;
+        let lock_timeout = Duration::from_millis(WALLET_LOCK_TIMEOUT_MS);
+
+        // 如果文件存在则读取，否则新建随机地址；新建时持有独占锁，避免两个进程同时创建
+        let lock = if wallet_file.exists() {
+            None
+        } else {
+            Some(WalletLock::acquire(
+                &wallet_file,
+                LockMode::Exclusive,
+                lock_timeout,
+            )?)
+        };
+        let data = if wallet_file.exists() {
+            drop(lock);
             tracing::info!("reading wallet : {:?}", wallet_file);
-            serde_json::from_str(&contents).expect("Failed to parse wallet file contents")
+            read_wallet_file(&wallet_file, password, permission_policy, lock_timeout)?
         } else {
-            let addr = FreeWebMovementAddress::random();
-            let json = serde_json::to_string_pretty(&addr).expect("Failed to serialize wallet");
-            let mut file =
-                std::fs::File::create(&wallet_file).expect("Failed to create wallet file");
-            file.write_all(json.as_bytes())
-                .expect("Failed to write wallet file");
-            addr
+            let data = WalletFile::single(WalletAccount::new(
+                DEFAULT_ACCOUNT_LABEL,
+                FreeWebMovementAddress::try_random()?,
+                AccountIndexes::default(),
+            ));
+            write_wallet_file(&wallet_file, &encode_wallet(&data, password)?)?;
+            data
         };
+        let (accounts, default_account) = data.into_accounts()?;
+
+        Ok(Self {
+            accounts,
+            default_account,
+            directory: dir.as_os_str().to_string_lossy().to_string(),
+            filename,
+            password: password.map(|p| Zeroizing::new(p.to_string())),
+            permission_policy,
+            lock_timeout,
+            retention: None,
+            auto_backup: false,
+        })
+    }
+
+    /// 用已有地址（如靓号）新建钱包文件；文件已存在时报错，避免覆盖原有钱包
+    pub fn create(
+        address: FreeWebMovementAddress,
+        directory: Option<&str>,
+        filename: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Self, AccountError> {
+        let (dir, filename, wallet_file) = prepare_wallet_path(directory, filename)?;
+        let lock_timeout = Duration::from_millis(WALLET_LOCK_TIMEOUT_MS);
+        let _lock = WalletLock::acquire(&wallet_file, LockMode::Exclusive, lock_timeout)?;
+        if wallet_file.exists() {
+            return Err(AccountError::Io(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("wallet file already exists: {:?}", wallet_file),
+            )));
+        }
 
-        Self {
+        let data = WalletFile::single(WalletAccount::new(
+            DEFAULT_ACCOUNT_LABEL,
             address,
+            AccountIndexes::default(),
+        ));
+        let (accounts, default_account) = data.into_accounts()?;
+        let wallet = Self {
+            accounts,
+            default_account,
             directory: dir.as_os_str().to_string_lossy().to_string(),
             filename,
+            password: password.map(|p| Zeroizing::new(p.to_string())),
+            permission_policy: PermissionPolicy::default(),
+            lock_timeout,
+            retention: None,
+            auto_backup: false,
+        };
+        write_wallet_file(
+            &wallet_file,
+            &encode_wallet(&wallet.wallet_file(), password)?,
+        )?;
+        Ok(wallet)
+    }
+
+    pub fn set_permission_policy(&mut self, policy: PermissionPolicy) {
+        self.permission_policy = policy;
+    }
+
+    /// 设置等待钱包锁的超时，默认 WALLET_LOCK_TIMEOUT_MS
+    pub fn set_lock_timeout(&mut self, timeout: Duration) {
+        self.lock_timeout = timeout;
+    }
+
+    /// 设置备份保留策略；设置后每次备份或快照都会清理同目录中过期的文件
+    pub fn set_backup_retention(&mut self, retention: Option<BackupRetention>) {
+        self.retention = retention;
+    }
+
+    /// 开启后，load、recovery、remove_account 覆盖内存中的账户之前先把当前状态写入快照
+    pub fn set_auto_backup(&mut self, enabled: bool) {
+        self.auto_backup = enabled;
+    }
+
+    fn lock(&self, mode: LockMode) -> Result<WalletLock, AccountError> {
+        WalletLock::acquire(Path::new(&self.to_absolute_path()), mode, self.lock_timeout)
+    }
+
+    /// 修复钱包目录下全部文件和子目录的权限，返回被修改的路径
+    pub fn repair_permissions(&self) -> Result<Vec<PathBuf>, AccountError> {
+        repair_permissions(Path::new(&self.directory))
+    }
+
+    /// 设置或清除口令，下一次 save / backup 时生效
+    pub fn set_password(&mut self, password: Option<&str>) {
+        self.password = password.map(|p| Zeroizing::new(p.to_string()));
+    }
+
+    fn password(&self) -> Option<&str> {
+        self.password.as_ref().map(|p| p.as_str())
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.password.is_some()
+    }
+
+    fn wallet_file(&self) -> WalletFile {
+        WalletFile {
+            version: WALLET_SCHEMA_VERSION,
+            accounts: self.accounts.clone(),
+            default_account: self.default_account().label.clone(),
+        }
+    }
+
+    fn apply(&mut self, data: WalletFile) -> Result<(), AccountError> {
+        (self.accounts, self.default_account) = data.into_accounts()?;
+        Ok(())
+    }
+
+    pub fn accounts(&self) -> &[WalletAccount] {
+        &self.accounts
+    }
+
+    pub fn account(&self, label: &str) -> Result<&WalletAccount, AccountError> {
+        Ok(&self.accounts[self.account_index(label)?])
+    }
+
+    fn account_mut(&mut self, label: &str) -> Result<&mut WalletAccount, AccountError> {
+        let index = self.account_index(label)?;
+        Ok(&mut self.accounts[index])
+    }
+
+    fn account_index(&self, label: &str) -> Result<usize, AccountError> {
+        self.accounts
+            .iter()
+            .position(|a| a.label == label)
+            .ok_or_else(|| account_error(format!("account \"{}\" does not exist", label)))
+    }
+
+    /// 默认账户，地址派生、xpub 等操作都作用于它
+    pub fn default_account(&self) -> &WalletAccount {
+        &self.accounts[self.default_account]
+    }
+
+    /// 默认账户的地址
+    pub fn address(&self) -> &FreeWebMovementAddress {
+        &self.default_account().address
+    }
+
+    /// 默认账户的地址，替代 0.2.0 前的 `wallet.address` 字段
+    #[deprecated(
+        since = "0.2.0",
+        note = "use address() to read, and add_account/remove_account to change accounts"
+    )]
+    pub fn address_mut(&mut self) -> &mut FreeWebMovementAddress {
+        &mut self.accounts[self.default_account].address
+    }
+
+    /// 默认账户的 HD 索引，替代 0.2.0 前的 `wallet.hd` 字段
+    #[deprecated(since = "0.2.0", note = "use default_account().hd to read")]
+    pub fn hd_mut(&mut self) -> &mut AccountIndexes {
+        &mut self.accounts[self.default_account].hd
+    }
+
+    fn check_new_label(&self, label: &str) -> Result<(), AccountError> {
+        if label.trim().is_empty() {
+            return Err(account_error("label is empty"));
+        }
+        if self.accounts.iter().any(|a| a.label == label) {
+            return Err(account_error(format!("label \"{}\" already exists", label)));
+        }
+        Ok(())
+    }
+
+    /// 添加使用独立助记词的账户；以下账户操作均在下一次 save 时写入文件
+    pub fn add_account(
+        &mut self,
+        label: &str,
+        address: FreeWebMovementAddress,
+    ) -> Result<&WalletAccount, AccountError> {
+        self.check_new_label(label)?;
+        if self
+            .accounts
+            .iter()
+            .any(|a| a.address.address == address.address)
+        {
+            return Err(account_error(format!(
+                "{} is already in the wallet",
+                address
+            )));
         }
+        self.accounts.push(WalletAccount::new(
+            label,
+            address,
+            AccountIndexes::default(),
+        ));
+        Ok(self.accounts.last().expect("just pushed"))
+    }
+
+    /// 用 from 账户的助记词派生新的 HD 账户 m/purpose'/coin_type'/account'
+    pub fn derive_account(
+        &mut self,
+        label: &str,
+        from: &str,
+        account: u32,
+        address_type: AddressType,
+        passphrase: &str,
+    ) -> Result<&WalletAccount, AccountError> {
+        self.check_new_label(label)?;
+        let source = self.account(from)?;
+        // 先校验 passphrase 能派生出来源账户的地址
+        let source_hd = HdAccount::from_address(&source.address, passphrase, source.hd)?;
+        let hd = HdAccount::new(
+            source_hd.mnemonic.clone(),
+            passphrase,
+            account,
+            source.address.info.network,
+            address_type,
+        );
+        let address = hd.derive(Chain::Receive, 0)?;
+        if self
+            .accounts
+            .iter()
+            .any(|a| a.address.address == address.address)
+        {
+            return Err(account_error(format!(
+                "{} is already in the wallet",
+                address
+            )));
+        }
+        self.accounts
+            .push(WalletAccount::new(label, address, hd.indexes));
+        Ok(self.accounts.last().expect("just pushed"))
+    }
+
+    /// 删除账户，不能删除最后一个；删除默认账户时第一个剩余账户成为默认账户
+    pub fn remove_account(&mut self, label: &str) -> Result<WalletAccount, AccountError> {
+        let index = self.account_index(label)?;
+        if self.accounts.len() == 1 {
+            return Err(account_error("cannot remove the last account"));
+        }
+        self.auto_snapshot()?;
+        let removed = self.accounts.remove(index);
+        if self.default_account == index {
+            self.default_account = 0;
+        } else if self.default_account > index {
+            self.default_account -= 1;
+        }
+        Ok(removed)
+    }
+
+    pub fn rename_account(&mut self, label: &str, new_label: &str) -> Result<(), AccountError> {
+        self.account(label)?;
+        self.check_new_label(new_label)?;
+        self.auto_snapshot()?;
+        self.account_mut(label)?.label = new_label.to_string();
+        Ok(())
+    }
+
+    pub fn set_default_account(&mut self, label: &str) -> Result<(), AccountError> {
+        let index = self.account_index(label)?;
+        self.auto_snapshot()?;
+        self.default_account = index;
+        Ok(())
+    }
+
+    /// 基于默认账户助记词的 HD 账户，passphrase 必须能派生出当前地址
+    pub fn hd_account(&self, passphrase: &str) -> Result<HdAccount, AccountError> {
+        let account = self.default_account();
+        HdAccount::from_address(&account.address, passphrase, account.hd)
+    }
+
+    /// 导出默认账户的扩展公钥，供只读钱包使用；passphrase 同 next_receive_address
+    pub fn account_xpub(&self, passphrase: &str) -> Result<Xpub, AccountError> {
+        self.hd_account(passphrase)?.xpub()
+    }
+
+    /// 派生默认账户的下一个收款地址并保存索引，避免重复使用地址；
+    /// passphrase 为创建钱包时的 BIP39 passphrase，没有设置时传空字符串
+    pub fn next_receive_address(
+        &mut self,
+        passphrase: &str,
+    ) -> Result<FreeWebMovementAddress, AccountError> {
+        self.next_address(Chain::Receive, passphrase)
+    }
+
+    /// 派生默认账户的下一个找零地址并保存索引
+    pub fn next_change_address(
+        &mut self,
+        passphrase: &str,
+    ) -> Result<FreeWebMovementAddress, AccountError> {
+        self.next_address(Chain::Change, passphrase)
+    }
+
+    /// 持有独占锁完成“读取索引、派生、保存”，并采用磁盘上更大的索引，
+    /// 避免多个进程打开同一钱包时派生出相同地址
+    fn next_address(
+        &mut self,
+        chain: Chain,
+        passphrase: &str,
+    ) -> Result<FreeWebMovementAddress, AccountError> {
+        let _lock = self.lock(LockMode::Exclusive)?;
+        self.refresh_indexes()?;
+        let mut account = self.hd_account(passphrase)?;
+        let address = match chain {
+            Chain::Receive => account.next_receive_address()?,
+            Chain::Change => account.next_change_address()?,
+        };
+        self.accounts[self.default_account].hd = account.indexes;
+        self.write()?;
+        Ok(address)
+    }
+
+    /// 用磁盘上同一账户更大的索引更新内存中的默认账户，其余未保存的修改保持不变
+    fn refresh_indexes(&mut self) -> Result<(), AccountError> {
+        let path = PathBuf::from(self.to_absolute_path());
+        if !path.exists() {
+            return Ok(());
+        }
+        let data = decode_wallet(&read_contents(&path)?, self.password())?;
+        let account = &mut self.accounts[self.default_account];
+        if let Some(saved) = data.accounts.iter().find(|a| {
+            a.label == account.label && a.address.to_string() == account.address.to_string()
+        }) {
+            account.hd.next_receive_index = account
+                .hd
+                .next_receive_index
+                .max(saved.hd.next_receive_index);
+            account.hd.next_change_index =
+                account.hd.next_change_index.max(saved.hd.next_change_index);
+        }
+        Ok(())
     }
 
     pub fn to_absolute_path(&self) -> String {
@@ -86,104 +715,158 @@ impl Wallet {
     }
 
     pub fn save(&self) -> std::io::Result<()> {
-        let mut file = std::fs::File::create(format!("{}", self.to_absolute_path()))?;
-        let json = serde_json::to_string(&self.address)?;
-        file.write(json.as_bytes())?;
-        Ok(())
+        Ok(self.try_save()?)
+    }
+
+    /// 持有独占锁保存钱包，其他进程正在读写时最多等待 lock_timeout
+    pub fn try_save(&self) -> Result<(), AccountError> {
+        let _lock = self.lock(LockMode::Exclusive)?;
+        self.write()
+    }
+
+    fn write(&self) -> Result<(), AccountError> {
+        let json = encode_wallet(&self.wallet_file(), self.password())?;
+        write_wallet_file(Path::new(&self.to_absolute_path()), &json)
     }
 
     pub fn load(&mut self) -> std::io::Result<()> {
-        let mut file = std::fs::File::open(format!("{}", self.to_absolute_path()))?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        self.address = serde_json::from_str(&contents)?;
-        Ok(())
+        Ok(self.try_load()?)
+    }
+
+    pub fn try_load(&mut self) -> Result<(), AccountError> {
+        self.auto_snapshot()?;
+        let data = read_wallet_file(
+            Path::new(&self.to_absolute_path()),
+            self.password(),
+            self.permission_policy,
+            self.lock_timeout,
+        )?;
+        self.apply(data)
     }
-    /// backup: 在指定路径生成 JSON，只保存 address
-    /// 如果 path 为 None，则在 Wallet 的默认目录生成带时间戳的文件
+
     pub fn backup(&self, path: Option<&str>) -> std::io::Result<String> {
-        // 生成 JSON 字符串
-        let json = serde_json::to_string_pretty(&self.address)?;
+        Ok(self.try_backup(path)?)
+    }
 
+    /// backup: 在指定路径生成 JSON，保存全部账户（设置了口令时同样加密）
+    /// 如果 path 为 None，则在 Wallet 的默认目录生成带时间戳的文件；设置了保留策略时随后清理过期备份
+    pub fn try_backup(&self, path: Option<&str>) -> Result<String, AccountError> {
         // 生成文件路径
         let backup_path = match path {
             Some(p) => {
                 let mut pb = PathBuf::from(p);
                 // 如果 p 是目录，则在目录下生成带时间戳的文件
                 if pb.is_dir() || p.ends_with('/') {
-                    let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S").to_string();
-                    pb.push(format!("wallet_backup_{}.json", timestamp));
-                } else if !pb.exists() {
-                    if let Some(parent) = pb.parent() {
-                        std::fs::create_dir_all(parent)?;
-                    }
+                    create_private_dir(&pb)?;
+                    pb = backup_file_path(&pb, BACKUP_PREFIX, chrono::Local::now().naive_local())?;
                 }
+                self.write_backup(&pb)?;
                 pb
             }
-            None => {
-                // 默认使用 Wallet 的目录
-                let mut pb = PathBuf::from(&self.directory);
-                let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S").to_string();
-                pb.push(format!("wallet_backup_{}.json", timestamp));
-                pb
-            }
+            // 默认使用 Wallet 的目录
+            None => self.write_timestamped(BACKUP_PREFIX)?,
         };
+        if let Some(retention) = &self.retention
+            && let Some(dir) = backup_path.parent()
+        {
+            prune_backups(dir, BACKUP_PREFIX, retention)?;
+        }
+
+        Ok(backup_path.to_string_lossy().to_string())
+    }
 
+    fn write_backup(&self, backup_path: &Path) -> Result<(), AccountError> {
+        let json = encode_wallet(&self.wallet_file(), self.password())?;
         // 确保父目录存在
         if let Some(parent) = backup_path.parent() {
-            std::fs::create_dir_all(parent)?;
+            create_private_dir(parent)?;
         }
+        let _lock = WalletLock::acquire(backup_path, LockMode::Exclusive, self.lock_timeout)?;
+        write_wallet_file(backup_path, &json)
+    }
 
-        // 写入文件
-        let mut file = std::fs::File::create(&backup_path)?;
-        file.write_all(json.as_bytes())?;
+    /// 在钱包目录写入带时间戳的备份文件
+    fn write_timestamped(&self, prefix: &str) -> Result<PathBuf, AccountError> {
+        let dir = PathBuf::from(&self.directory);
+        create_private_dir(&dir)?;
+        let backup_path = backup_file_path(&dir, prefix, chrono::Local::now().naive_local())?;
+        self.write_backup(&backup_path)?;
+        Ok(backup_path)
+    }
 
-        Ok(backup_path.to_string_lossy().to_string())
+    /// 立即把当前全部账户写入钱包目录的快照文件，按保留策略清理旧快照，返回快照路径
+    pub fn snapshot(&self) -> Result<String, AccountError> {
+        let snapshot_path = self.write_timestamped(SNAPSHOT_PREFIX)?;
+        if let Some(retention) = &self.retention {
+            prune_backups(Path::new(&self.directory), SNAPSHOT_PREFIX, retention)?;
+        }
+        tracing::info!("wallet snapshot written to {:?}", snapshot_path);
+        Ok(snapshot_path.to_string_lossy().to_string())
+    }
+
+    fn auto_snapshot(&self) -> Result<(), AccountError> {
+        if self.auto_backup {
+            self.snapshot()?;
+        }
+        Ok(())
+    }
+
+    /// 钱包目录中的快照文件，按时间从旧到新排列；用 restore(Some(路径)) 撤销误操作
+    pub fn snapshots(&self) -> Result<Vec<PathBuf>, AccountError> {
+        Ok(list_backups(Path::new(&self.directory), SNAPSHOT_PREFIX)?
+            .into_iter()
+            .map(|b| b.path)
+            .collect())
     }
 
-    /// recovery: 从指定 backup 文件恢复 address
-    /// 如果 path 为 None，则从 Wallet 默认目录的最新备份文件恢复
     pub fn recovery(&mut self, path: Option<&str>) -> std::io::Result<()> {
+        Ok(self.try_recovery(path)?)
+    }
+
+    /// recovery: 从指定 backup 文件恢复全部账户
+    /// 如果 path 为 None，则从 Wallet 默认目录的最新备份文件恢复（不包括自动快照）
+    pub fn try_recovery(&mut self, path: Option<&str>) -> Result<(), AccountError> {
         let backup_path = match path {
             Some(p) => PathBuf::from(p),
             None => {
                 // 默认目录，选择最新备份文件
                 let dir = PathBuf::from(&self.directory);
-                let mut entries: Vec<_> = std::fs::read_dir(&dir)?
-                    .filter_map(|e| e.ok())
-                    .filter(|e| {
-                        e.file_type().map(|ft| ft.is_file()).unwrap_or(false)
-                            && e.file_name()
-                                .to_string_lossy()
-                                .starts_with("wallet_backup_")
-                            && e.file_name().to_string_lossy().ends_with(".json")
-                    })
-                    .collect();
-                // 按文件名排序（时间戳靠前），取最后一个
-                entries.sort_by_key(|e| e.file_name());
-                if let Some(entry) = entries.pop() {
-                    entry.path()
-                } else {
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        "没有找到备份文件",
-                    ));
+                match list_backups(&dir, BACKUP_PREFIX)?.pop() {
+                    Some(backup) => backup.path,
+                    None => {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            "没有找到备份文件",
+                        )
+                        .into());
+                    }
                 }
             }
         };
 
-        // 读取文件
-        let mut file = std::fs::File::open(&backup_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        self.address = serde_json::from_str(&contents)?;
-        Ok(())
+        // 读取文件，旧版本备份只在内存中升级，不改写备份文件
+        check_permissions(&backup_path, self.permission_policy)?;
+        let contents = {
+            let _lock = WalletLock::acquire(&backup_path, LockMode::Shared, self.lock_timeout)?;
+            read_contents(&backup_path)?
+        };
+        let data = decode_wallet(&contents, self.password())?;
+        // 备份可读后再生成快照，恢复错误的备份时可以撤销
+        self.auto_snapshot()?;
+        self.apply(data)
+    }
+
+    /// 从备份恢复并写入钱包文件；recovery 只修改内存中的账户
+    pub fn restore(&mut self, path: Option<&str>) -> Result<(), AccountError> {
+        self.try_recovery(path)?;
+        self.try_save()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::address::MnemonicInfo;
     use std::fs;
 
     #[test]
@@ -197,7 +880,7 @@ mod tests {
 
         // 创建钱包
         let wallet = Wallet::new(Some(test_dir), Some(test_file));
-        let addr1 = wallet.address.to_string();
+        let addr1 = wallet.address().to_string();
 
         // 保存钱包
         wallet.save().expect("保存钱包失败");
@@ -205,11 +888,19 @@ mod tests {
         // 加载钱包
         let mut loaded_wallet = Wallet::new(Some(test_dir), Some(test_file));
         loaded_wallet.load().expect("加载钱包失败");
-        let addr2 = loaded_wallet.address.to_string();
+        let addr2 = loaded_wallet.address().to_string();
 
         // 检查地址一致
         assert_eq!(addr1, addr2);
 
+        // 用已有地址新建钱包，不覆盖已存在的文件
+        let address = FreeWebMovementAddress::random();
+        let expected = address.to_string();
+        assert!(Wallet::create(address.clone(), Some(test_dir), Some(test_file), None).is_err());
+        Wallet::create(address, Some(test_dir), Some("created.json"), None).unwrap();
+        let created = Wallet::new(Some(test_dir), Some("created.json"));
+        assert_eq!(created.address().to_string(), expected);
+
         // 清理
         let _ = fs::remove_dir_all(test_dir);
 
@@ -228,7 +919,7 @@ mod tests {
 
         // 1. 创建钱包
         let wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
-        let original_address = wallet.address.to_string();
+        let original_address = wallet.address().to_string();
 
         // 2. 保存钱包
         wallet.save().expect("保存钱包失败");
@@ -236,7 +927,7 @@ mod tests {
         // 3. 加载钱包
         let mut loaded_wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
         loaded_wallet.load().expect("加载钱包失败");
-        assert_eq!(original_address, loaded_wallet.address.to_string());
+        assert_eq!(original_address, loaded_wallet.address().to_string());
 
         // 4. backup 指定绝对路径
         let backup_path_abs = format!("{}/backup_abs.json", tmp_dir);
@@ -255,8 +946,8 @@ mod tests {
             .recovery(Some(&backup_path_abs))
             .expect("指定路径恢复失败");
         assert_eq!(
-            wallet_recovered_abs.address.to_string(),
-            loaded_wallet.address.to_string()
+            wallet_recovered_abs.address().to_string(),
+            loaded_wallet.address().to_string()
         );
 
         // 7. recovery 默认路径（使用最后生成的备份文件）
@@ -265,11 +956,412 @@ mod tests {
             .recovery(None)
             .expect("默认路径恢复失败");
         assert_eq!(
-            wallet_recovered_default.address.to_string(),
-            loaded_wallet.address.to_string()
+            wallet_recovered_default.address().to_string(),
+            loaded_wallet.address().to_string()
         );
 
         // 清理临时目录
         let _ = fs::remove_dir_all(tmp_dir);
     }
+
+    #[test]
+    fn test_wallet_encrypted_save_load_and_migration() {
+        let tmp_dir = "/tmp/test_wallet_encrypted";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        // 1. 先创建明文钱包（旧格式）
+        let plain = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        let original_address = plain.address().to_string();
+        let mnemonic = plain.address().mnemonic.to_string();
+
+        // 2. 带口令打开明文钱包并保存，完成迁移
+        let mut wallet = Wallet::open(Some(tmp_dir), Some(wallet_file), Some("secret")).unwrap();
+        assert_eq!(original_address, wallet.address().to_string());
+        wallet.save().expect("加密保存失败");
+
+        let contents = fs::read_to_string(wallet.to_absolute_path()).unwrap();
+        assert!(Keystore::is_keystore(&contents));
+        assert!(!contents.contains(&mnemonic));
+
+        // 3. 无口令或错误口令无法打开
+        assert!(Wallet::open(Some(tmp_dir), Some(wallet_file), None).is_err());
+        assert!(Wallet::open(Some(tmp_dir), Some(wallet_file), Some("wrong")).is_err());
+
+        // 4. 正确口令读取
+        wallet.load().expect("加载加密钱包失败");
+        let reopened = Wallet::open(Some(tmp_dir), Some(wallet_file), Some("secret")).unwrap();
+        assert_eq!(original_address, reopened.address().to_string());
+
+        // 5. 加密备份与恢复
+        let backup = reopened.backup(None).unwrap();
+        assert!(Keystore::is_keystore(&fs::read_to_string(&backup).unwrap()));
+        let mut recovered = Wallet::open(Some(tmp_dir), Some(wallet_file), Some("secret")).unwrap();
+        recovered.recovery(Some(&backup)).expect("恢复加密备份失败");
+        assert_eq!(original_address, recovered.address().to_string());
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_persists_hd_indexes() {
+        let tmp_dir = "/tmp/test_wallet_hd_indexes";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let mut wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        let first = wallet.next_receive_address("").expect("派生收款地址失败");
+        let second = wallet.next_receive_address("").expect("派生收款地址失败");
+        let change = wallet.next_change_address("").expect("派生找零地址失败");
+        assert_eq!(first.to_string(), wallet.address().to_string());
+        assert_ne!(first.to_string(), second.to_string());
+        assert_ne!(second.to_string(), change.to_string());
+
+        // 重新打开后索引仍然保留
+        let mut reopened = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        assert_eq!(reopened.default_account().hd.next_receive_index, 2);
+        assert_eq!(reopened.default_account().hd.next_change_index, 1);
+        let third = reopened.next_receive_address("").unwrap();
+        assert_eq!(third.info.derivation_path, "m/44'/1010086'/0'/0/2");
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_hd_with_passphrase() {
+        let tmp_dir = "/tmp/test_wallet_hd_passphrase";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let mnemonic_info = MnemonicInfo {
+            language: bip39::Language::English,
+            word_count: 12,
+            phrase: String::new().into(),
+            passphrase: "correct horse".to_string().into(),
+        };
+        let address = FreeWebMovementAddress::try_new(mnemonic_info, None).unwrap();
+        assert!(address.mnemonic_meta().has_passphrase);
+        let mut wallet = Wallet::create(address.clone(), Some(tmp_dir), None, None).unwrap();
+
+        // 缺少或错误的 passphrase 无法派生，索引不变
+        assert!(matches!(
+            wallet.next_receive_address(""),
+            Err(AccountError::PassphraseMismatch)
+        ));
+        assert!(wallet.next_change_address("wrong").is_err());
+        assert_eq!(wallet.default_account().hd.next_receive_index, 0);
+
+        let first = wallet.next_receive_address("correct horse").unwrap();
+        assert_eq!(first.to_string(), address.to_string());
+        let change = wallet.next_change_address("correct horse").unwrap();
+        assert_eq!(change.info.derivation_path, "m/44'/1010086'/0'/1/0");
+        wallet.load().unwrap();
+        assert_eq!(wallet.default_account().hd.next_receive_index, 1);
+
+        assert!(wallet.account_xpub("").is_err());
+        let xpub = wallet.account_xpub("correct horse").unwrap();
+        let hd = HdAccount::from_address(&address, "correct horse", AccountIndexes::default());
+        assert_eq!(xpub, hd.unwrap().xpub().unwrap());
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_accounts() {
+        let tmp_dir = "/tmp/test_wallet_accounts";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let mut wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        assert_eq!(wallet.default_account().label, DEFAULT_ACCOUNT_LABEL);
+        let main = wallet.address().to_string();
+
+        // 独立助记词账户与派生账户
+        let savings = FreeWebMovementAddress::random();
+        wallet.add_account("savings", savings.clone()).unwrap();
+        assert!(wallet.add_account("savings", savings.clone()).is_err());
+        assert!(wallet.add_account("copy", savings).is_err());
+        let derived = wallet
+            .derive_account("work", DEFAULT_ACCOUNT_LABEL, 1, AddressType::P2tr, "")
+            .unwrap();
+        assert_eq!(
+            derived.address.info.derivation_path,
+            "m/86'/1010086'/1'/0/0"
+        );
+        assert_eq!(derived.hd.account, 1);
+        assert!(
+            wallet
+                .derive_account("work2", DEFAULT_ACCOUNT_LABEL, 1, AddressType::P2tr, "")
+                .is_err()
+        );
+
+        wallet.rename_account("work", "business").unwrap();
+        assert!(wallet.rename_account("business", "savings").is_err());
+        wallet.set_default_account("business").unwrap();
+        let receive = wallet.next_receive_address("").unwrap();
+        assert_eq!(receive.to_string(), wallet.address().to_string());
+        let backup = wallet.backup(None).unwrap();
+
+        // 重新打开后保留全部账户与默认账户
+        let mut reopened = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        assert_eq!(reopened.accounts().len(), 3);
+        assert_eq!(reopened.default_account().label, "business");
+        assert_eq!(reopened.default_account().hd.next_receive_index, 1);
+
+        // 已弃用的访问器作用于默认账户
+        #[allow(deprecated)]
+        {
+            assert_eq!(reopened.hd_mut().next_receive_index, 1);
+            assert_eq!(
+                reopened.address_mut().to_string(),
+                reopened.address().to_string()
+            );
+        }
+        assert_eq!(
+            reopened
+                .account(DEFAULT_ACCOUNT_LABEL)
+                .unwrap()
+                .address
+                .to_string(),
+            main
+        );
+
+        // 删除排在前面的账户后默认账户不变；删除默认账户后第一个账户成为默认账户，不能删除最后一个
+        reopened.remove_account("savings").unwrap();
+        assert_eq!(reopened.default_account().label, "business");
+        reopened.rename_account("business", "work").unwrap();
+        assert_eq!(reopened.default_account().label, "work");
+        reopened.remove_account("work").unwrap();
+        assert_eq!(reopened.default_account().label, DEFAULT_ACCOUNT_LABEL);
+        assert!(reopened.remove_account(DEFAULT_ACCOUNT_LABEL).is_err());
+        reopened.recovery(Some(&backup)).unwrap();
+        assert_eq!(reopened.accounts().len(), 3);
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_reads_legacy_single_address_file() {
+        let tmp_dir = "/tmp/test_wallet_legacy";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+        fs::create_dir_all(tmp_dir).unwrap();
+
+        let address = FreeWebMovementAddress::random();
+        let expected = address.to_string();
+        address
+            .save_to_file(&format!("{}/{}", tmp_dir, wallet_file))
+            .unwrap();
+
+        let wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        assert_eq!(wallet.accounts().len(), 1);
+        assert_eq!(wallet.default_account().label, DEFAULT_ACCOUNT_LABEL);
+        assert_eq!(wallet.address().to_string(), expected);
+
+        // 原位置升级到当前版本，并保留升级前的文件
+        let path = format!("{}/{}", tmp_dir, wallet_file);
+        let upgraded: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(upgraded["version"], WALLET_SCHEMA_VERSION);
+        let original = fs::read_to_string(format!("{}.v0.bak", path)).unwrap();
+        assert!(original.contains("\"mnemonic\""));
+        assert!(!original.contains("\"version\""));
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_schema_versions() {
+        let account = WalletAccount::new(
+            DEFAULT_ACCOUNT_LABEL,
+            FreeWebMovementAddress::random(),
+            AccountIndexes::default(),
+        );
+        let current = WalletFile::single(account);
+        let json = encode_wallet(&current, None).unwrap();
+        let (_, version) = decode_wallet_versioned(&json, None).unwrap();
+        assert_eq!(version, WALLET_SCHEMA_VERSION);
+
+        // 无版本号的多账户格式
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("version");
+        let (data, version) = decode_wallet_versioned(&value.to_string(), None).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(data.version, WALLET_SCHEMA_VERSION);
+
+        // 更新版本写入的文件被拒绝
+        value["version"] = serde_json::json!(WALLET_SCHEMA_VERSION + 1);
+        let err = decode_wallet(&value.to_string(), None).unwrap_err();
+        assert!(matches!(err, AccountError::WalletSchema(_)));
+        assert!(err.to_string().contains("newer"));
+        value["version"] = serde_json::json!("two");
+        assert!(decode_wallet(&value.to_string(), None).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wallet_permission_policy() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = "/tmp/test_wallet_permissions";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        let backup = wallet.backup(None).unwrap();
+        for path in [wallet.to_absolute_path(), backup.clone()] {
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        }
+
+        let strict = PermissionPolicy::Strict;
+        assert!(Wallet::open_with_policy(Some(tmp_dir), Some(wallet_file), None, strict).is_err());
+        let mut warned = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        warned.set_permission_policy(strict);
+        assert!(warned.recovery(Some(&backup)).is_err());
+
+        assert_eq!(warned.repair_permissions().unwrap().len(), 2);
+        let mut reopened =
+            Wallet::open_with_policy(Some(tmp_dir), Some(wallet_file), None, strict).unwrap();
+        reopened.recovery(Some(&backup)).unwrap();
+        assert_eq!(reopened.address().to_string(), wallet.address().to_string());
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_locking() {
+        let tmp_dir = "/tmp/test_wallet_locking";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        // 两个句柄打开同一钱包，派生的地址不重复
+        let mut first = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        let mut second = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        let a = first.next_receive_address("").unwrap();
+        let b = second.next_receive_address("").unwrap();
+        assert_ne!(a.to_string(), b.to_string());
+        first.load().unwrap();
+        assert_eq!(first.default_account().hd.next_receive_index, 2);
+
+        // 其他句柄持有独占锁时，保存和读取在超时后报告持有者
+        let path = PathBuf::from(first.to_absolute_path());
+        let lock = WalletLock::acquire(&path, LockMode::Exclusive, Duration::ZERO).unwrap();
+        first.set_lock_timeout(Duration::from_millis(100));
+        let err = first.try_save().unwrap_err();
+        assert!(matches!(err, AccountError::WalletLocked(_)));
+        assert!(err.to_string().contains("in use by PID"));
+        // 返回 io::Result 的旧接口保留原错误信息
+        let err = first.save().unwrap_err();
+        assert!(err.to_string().contains("in use by PID"));
+        assert!(first.try_load().is_err());
+        drop(lock);
+        first.save().unwrap();
+
+        // 备份文件同样加锁：写入备份时持有独占锁，从备份恢复时持有共享锁
+        let backup = format!("{}/locked_backup.json", tmp_dir);
+        first.try_backup(Some(&backup)).unwrap();
+        let lock =
+            WalletLock::acquire(Path::new(&backup), LockMode::Exclusive, Duration::ZERO).unwrap();
+        assert!(matches!(
+            first.try_backup(Some(&backup)),
+            Err(AccountError::WalletLocked(_))
+        ));
+        assert!(matches!(
+            first.try_recovery(Some(&backup)),
+            Err(AccountError::WalletLocked(_))
+        ));
+        drop(lock);
+        let shared =
+            WalletLock::acquire(Path::new(&backup), LockMode::Shared, Duration::ZERO).unwrap();
+        first.try_recovery(Some(&backup)).unwrap();
+        assert!(first.try_backup(Some(&backup)).is_err());
+        drop(shared);
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_wallet_backup_retention_and_snapshots() {
+        let tmp_dir = "/tmp/test_wallet_backup_retention";
+        let wallet_file = "wallet.json";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let mut wallet = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        wallet.set_backup_retention(Some(BackupRetention {
+            keep_last: 2,
+            ..Default::default()
+        }));
+        let backups: Vec<String> = (0..4).map(|_| wallet.backup(None).unwrap()).collect();
+        let kept = list_backups(Path::new(tmp_dir), BACKUP_PREFIX).unwrap();
+        let kept: Vec<String> = kept
+            .iter()
+            .map(|b| b.path.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(kept, backups[2..]);
+
+        // 恢复了错误的备份后，可以从自动快照撤销
+        let original = wallet.address().to_string();
+        let other = Wallet::new(Some(tmp_dir), Some("other.json"));
+        let other_backup = other.backup(Some(&format!("{}/other_backup.json", tmp_dir)));
+        assert!(wallet.snapshots().unwrap().is_empty());
+        wallet.recovery(Some(&other_backup.unwrap())).unwrap();
+        assert!(wallet.snapshots().unwrap().is_empty());
+
+        wallet.load().unwrap();
+        wallet.set_auto_backup(true);
+        assert!(
+            wallet
+                .recovery(Some("/tmp/test_wallet_backup_missing.json"))
+                .is_err()
+        );
+        assert!(wallet.snapshots().unwrap().is_empty());
+        wallet
+            .restore(Some(&format!("{}/other_backup.json", tmp_dir)))
+            .unwrap();
+        let other_address = other.address().to_string();
+        assert_eq!(wallet.address().to_string(), other_address);
+        let reopened = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        assert_eq!(reopened.address().to_string(), other_address);
+
+        // restore 写入了钱包文件，从快照撤销后重新打开仍是原地址
+        let snapshots = wallet.snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        wallet
+            .restore(Some(&snapshots[0].to_string_lossy()))
+            .unwrap();
+        assert_eq!(wallet.address().to_string(), original);
+        let reopened = Wallet::new(Some(tmp_dir), Some(wallet_file));
+        assert_eq!(reopened.address().to_string(), original);
+
+        // 快照不参与 recovery(None) 的最新备份选择，并按同一策略保留
+        wallet.load().unwrap();
+        assert_eq!(wallet.snapshots().unwrap().len(), 2);
+        wallet.snapshot().unwrap();
+        assert_eq!(wallet.snapshots().unwrap().len(), 2);
+        wallet.recovery(None).unwrap();
+        assert_eq!(wallet.address().to_string(), original);
+
+        // 重命名和切换默认账户同样先生成快照，失败的操作不生成
+        wallet.set_backup_retention(None);
+        wallet
+            .add_account("savings", FreeWebMovementAddress::random())
+            .unwrap();
+        assert!(
+            wallet
+                .rename_account("savings", DEFAULT_ACCOUNT_LABEL)
+                .is_err()
+        );
+        assert!(wallet.set_default_account("missing").is_err());
+        assert_eq!(wallet.snapshots().unwrap().len(), 2);
+        wallet.rename_account("savings", "spending").unwrap();
+        assert_eq!(wallet.snapshots().unwrap().len(), 3);
+        wallet.set_default_account("spending").unwrap();
+        assert_eq!(wallet.snapshots().unwrap().len(), 4);
+        wallet
+            .recovery(Some(&wallet.snapshots().unwrap()[3].to_string_lossy()))
+            .unwrap();
+        assert_eq!(wallet.default_account().label, DEFAULT_ACCOUNT_LABEL);
+        assert!(wallet.account("spending").is_ok());
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
 }
@@ -0,0 +1,420 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use bitcoin::address::AddressType;
+use bitcoin::bip32::{ChildNumber, Xpub};
+use bitcoin::{Address, Network, PublicKey};
+use secp256k1::Secp256k1;
+use serde::{Deserialize, Serialize};
+
+use crate::account::{AccountIndexes, Chain};
+use crate::address::FreeWebMovementAddress;
+use crate::consts::{COIN_PREFIX, DEFAULT_WATCH_ONLY_FILE, WALLET_LOCK_TIMEOUT_MS};
+use crate::error::AccountError;
+use crate::storage::{
+    LockMode, PermissionPolicy, WalletLock, atomic_write, check_permissions, create_private_dir,
+};
+use crate::wallet::resolve_wallet_dir;
+
+/// 只读钱包文件内容：只有账户层 xpub，不含助记词和私钥
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchOnlyFile {
+    #[serde(with = "crate::address::serde_xpub")]
+    pub xpub: Xpub,
+    #[serde(with = "crate::address::serde_address_type")]
+    pub address_type: AddressType,
+    pub prefix: String,
+    /// xpub 只区分主网与测试网，regtest/signet 需要单独保存；旧文件中缺失时取 xpub 的网络
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::address::serde_option_network"
+    )]
+    pub network: Option<Network>,
+    #[serde(default)]
+    pub hd: AccountIndexes,
+}
+
+/// 与钱包文件相同：先按 policy 检查权限，读取时持有共享锁
+fn read_watch_only_file(
+    path: &Path,
+    policy: PermissionPolicy,
+    lock_timeout: Duration,
+) -> Result<WatchOnlyFile, AccountError> {
+    check_permissions(path, policy)?;
+    let _lock = WalletLock::acquire(path, LockMode::Shared, lock_timeout)?;
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// 只读钱包：从 xpub 派生非硬化的 change/index 子地址
+pub struct WatchOnlyWallet {
+    pub xpub: Xpub,
+    pub address_type: AddressType,
+    pub network: Network,
+    pub prefix: String,
+    pub hd: AccountIndexes,
+    pub directory: String,
+    pub filename: String,
+    permission_policy: PermissionPolicy,
+    lock_timeout: Duration,
+}
+
+impl WatchOnlyWallet {
+    /// 从 xpub 新建只读钱包并保存；文件已存在时报错，避免重置已保存的索引导致地址重复使用，
+    /// 已有的只读钱包用 open 打开。network 须与 xpub 一致：主网 xpub 只能用于 Bitcoin，
+    /// 测试网 tpub 可用于 Testnet、Signet、Regtest
+    pub fn from_xpub(
+        xpub: &str,
+        address_type: AddressType,
+        network: Network,
+        directory: Option<&str>,
+        filename: Option<&str>,
+    ) -> Result<Self, AccountError> {
+        let xpub = Xpub::from_str(xpub)?;
+        if (network == Network::Bitcoin) != (xpub.network == Network::Bitcoin) {
+            return Err(AccountError::NetworkMismatch(format!(
+                "xpub for {} cannot be used on {}",
+                xpub.network, network
+            )));
+        }
+        let dir = resolve_wallet_dir(directory);
+        create_private_dir(&dir)?;
+
+        let wallet = WatchOnlyWallet {
+            xpub,
+            address_type,
+            network,
+            prefix: COIN_PREFIX.to_string(),
+            hd: AccountIndexes::default(),
+            directory: dir.to_string_lossy().to_string(),
+            filename: filename.unwrap_or(DEFAULT_WATCH_ONLY_FILE).to_string(),
+            permission_policy: PermissionPolicy::default(),
+            lock_timeout: Duration::from_millis(WALLET_LOCK_TIMEOUT_MS),
+        };
+        // 持有独占锁检查并写入，避免两个进程同时导入
+        let _lock = wallet.lock(LockMode::Exclusive)?;
+        let path = PathBuf::from(wallet.to_absolute_path());
+        if path.exists() {
+            return Err(AccountError::Io(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("watch-only wallet already exists: {:?}", path),
+            )));
+        }
+        // 提前检查地址类型是否支持
+        wallet.derive(Chain::Receive, 0)?;
+        wallet.write()?;
+        Ok(wallet)
+    }
+
+    /// 打开已保存的只读钱包
+    pub fn open(directory: Option<&str>, filename: Option<&str>) -> Result<Self, AccountError> {
+        WatchOnlyWallet::open_with_policy(directory, filename, PermissionPolicy::default())
+    }
+
+    /// 打开已保存的只读钱包，并按 policy 处理权限过宽的文件
+    pub fn open_with_policy(
+        directory: Option<&str>,
+        filename: Option<&str>,
+        permission_policy: PermissionPolicy,
+    ) -> Result<Self, AccountError> {
+        let dir = resolve_wallet_dir(directory);
+        let filename = filename.unwrap_or(DEFAULT_WATCH_ONLY_FILE).to_string();
+        let mut path = dir.clone();
+        path.push(&filename);
+
+        let lock_timeout = Duration::from_millis(WALLET_LOCK_TIMEOUT_MS);
+        let data = read_watch_only_file(&path, permission_policy, lock_timeout)?;
+        Ok(WatchOnlyWallet {
+            xpub: data.xpub,
+            address_type: data.address_type,
+            network: data.network.unwrap_or(data.xpub.network),
+            prefix: data.prefix,
+            hd: data.hd,
+            directory: dir.to_string_lossy().to_string(),
+            filename,
+            permission_policy,
+            lock_timeout,
+        })
+    }
+
+    pub fn set_permission_policy(&mut self, policy: PermissionPolicy) {
+        self.permission_policy = policy;
+    }
+
+    /// 设置等待文件锁的超时，默认 WALLET_LOCK_TIMEOUT_MS
+    pub fn set_lock_timeout(&mut self, timeout: Duration) {
+        self.lock_timeout = timeout;
+    }
+
+    fn lock(&self, mode: LockMode) -> Result<WalletLock, AccountError> {
+        WalletLock::acquire(Path::new(&self.to_absolute_path()), mode, self.lock_timeout)
+    }
+
+    /// 从文件重新读取，丢弃内存中未保存的修改
+    pub fn load(&mut self) -> Result<(), AccountError> {
+        let data = read_watch_only_file(
+            Path::new(&self.to_absolute_path()),
+            self.permission_policy,
+            self.lock_timeout,
+        )?;
+        self.xpub = data.xpub;
+        self.address_type = data.address_type;
+        self.network = data.network.unwrap_or(data.xpub.network);
+        self.prefix = data.prefix;
+        self.hd = data.hd;
+        Ok(())
+    }
+
+    pub fn to_absolute_path(&self) -> String {
+        let mut path = PathBuf::from(&self.directory);
+        path.push(&self.filename);
+        path.to_string_lossy().to_string()
+    }
+
+    /// 持有独占锁保存，其他进程正在读写时最多等待 lock_timeout
+    pub fn save(&self) -> Result<(), AccountError> {
+        let _lock = self.lock(LockMode::Exclusive)?;
+        self.write()
+    }
+
+    fn write(&self) -> Result<(), AccountError> {
+        let data = WatchOnlyFile {
+            xpub: self.xpub,
+            address_type: self.address_type,
+            network: Some(self.network),
+            prefix: self.prefix.clone(),
+            hd: self.hd,
+        };
+        let json = serde_json::to_string_pretty(&data)?;
+        atomic_write(Path::new(&self.to_absolute_path()), json.as_bytes())
+    }
+
+    /// 派生 xpub/change/index 的公钥
+    pub fn derive_public_key(&self, chain: Chain, index: u32) -> Result<PublicKey, AccountError> {
+        let secp = Secp256k1::verification_only();
+        let path = [
+            ChildNumber::from_normal_idx(chain as u32)?,
+            ChildNumber::from_normal_idx(index)?,
+        ];
+        let child = self.xpub.derive_pub(&secp, &path)?;
+        Ok(PublicKey::new(child.public_key))
+    }
+
+    pub fn derive(&self, chain: Chain, index: u32) -> Result<Address, AccountError> {
+        let key = self.derive_public_key(chain, index)?;
+        FreeWebMovementAddress::try_key_to_inner_address(key, self.network, self.address_type)
+    }
+
+    /// 带前缀的地址字符串，与 FreeWebMovementAddress 的 Display 一致
+    pub fn to_prefixed(&self, address: &Address) -> String {
+        format!("{}:{}", self.prefix, address)
+    }
+
+    /// 派生下一个收款地址并保存索引
+    pub fn next_receive_address(&mut self) -> Result<Address, AccountError> {
+        self.next_address(Chain::Receive)
+    }
+
+    /// 派生下一个找零地址并保存索引
+    pub fn next_change_address(&mut self) -> Result<Address, AccountError> {
+        self.next_address(Chain::Change)
+    }
+
+    /// 与 Wallet 相同，持有独占锁完成“读取索引、派生、保存”，并采用磁盘上更大的索引
+    fn next_address(&mut self, chain: Chain) -> Result<Address, AccountError> {
+        let _lock = self.lock(LockMode::Exclusive)?;
+        let path = PathBuf::from(self.to_absolute_path());
+        if path.exists() {
+            let saved: WatchOnlyFile = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            if saved.xpub == self.xpub {
+                self.hd.next_receive_index =
+                    self.hd.next_receive_index.max(saved.hd.next_receive_index);
+                self.hd.next_change_index =
+                    self.hd.next_change_index.max(saved.hd.next_change_index);
+            }
+        }
+        let index = match chain {
+            Chain::Receive => self.hd.next_receive_index,
+            Chain::Change => self.hd.next_change_index,
+        };
+        let address = self.derive(chain, index)?;
+        match chain {
+            Chain::Receive => self.hd.next_receive_index += 1,
+            Chain::Change => self.hd.next_change_index += 1,
+        }
+        self.write()?;
+        Ok(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::HdAccount;
+    use std::fs;
+
+    #[test]
+    fn test_watch_only_matches_hd_account() {
+        let tmp_dir = "/tmp/test_watch_only_wallet";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let address = FreeWebMovementAddress::random();
+        let account = HdAccount::from_address(&address, "", AccountIndexes::default()).unwrap();
+        let xpub = account.xpub().expect("导出 xpub 失败");
+        assert!(xpub.to_string().starts_with("xpub"));
+
+        let mut watch_only = WatchOnlyWallet::from_xpub(
+            &xpub.to_string(),
+            AddressType::P2pkh,
+            Network::Bitcoin,
+            Some(tmp_dir),
+            None,
+        )
+        .expect("创建只读钱包失败");
+        for index in 0..3 {
+            let expected = account.derive(Chain::Receive, index).unwrap();
+            assert_eq!(watch_only.next_receive_address().unwrap(), expected.address);
+        }
+        let change = account.derive(Chain::Change, 0).unwrap();
+        let next_change = watch_only.next_change_address().unwrap();
+        assert_eq!(watch_only.to_prefixed(&next_change), change.to_string());
+
+        // 文件中没有助记词和私钥
+        let contents = fs::read_to_string(watch_only.to_absolute_path()).unwrap();
+        assert!(!contents.contains("mnemonic"));
+        assert!(!contents.contains("private_key"));
+        assert!(!contents.contains(&address.mnemonic.to_string()));
+
+        let mut reopened = WatchOnlyWallet::open(Some(tmp_dir), None).unwrap();
+        assert_eq!(reopened.hd.next_receive_index, 3);
+        assert_eq!(reopened.hd.next_change_index, 1);
+
+        // 再次导入同一 xpub 报错，已保存的索引不被重置
+        let again = WatchOnlyWallet::from_xpub(
+            &xpub.to_string(),
+            AddressType::P2pkh,
+            Network::Bitcoin,
+            Some(tmp_dir),
+            None,
+        );
+        assert!(matches!(again, Err(AccountError::Io(_))));
+        reopened.hd = AccountIndexes::default();
+        reopened.load().unwrap();
+        assert_eq!(reopened.hd.next_receive_index, 3);
+        assert_eq!(reopened.hd.next_change_index, 1);
+
+        // 主网 xpub 不能用于测试网
+        assert!(matches!(
+            WatchOnlyWallet::from_xpub(
+                &xpub.to_string(),
+                AddressType::P2pkh,
+                Network::Regtest,
+                Some(tmp_dir),
+                Some("regtest.json")
+            ),
+            Err(AccountError::NetworkMismatch(_))
+        ));
+        assert!(
+            WatchOnlyWallet::from_xpub(
+                "xpub-invalid",
+                AddressType::P2pkh,
+                Network::Bitcoin,
+                Some(tmp_dir),
+                None
+            )
+            .is_err()
+        );
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_watch_only_regtest_p2wpkh() {
+        let tmp_dir = "/tmp/test_watch_only_regtest";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let mnemonic = bip39::Mnemonic::generate_in(bip39::Language::English, 12).unwrap();
+        let account = HdAccount::new(mnemonic, "", 0, Network::Regtest, AddressType::P2wpkh);
+        let xpub = account.xpub().unwrap();
+        assert!(xpub.to_string().starts_with("tpub"));
+
+        // tpub 本身无法区分 testnet 与 regtest，派生地址使用保存的网络
+        let mut watch_only = WatchOnlyWallet::from_xpub(
+            &xpub.to_string(),
+            AddressType::P2wpkh,
+            Network::Regtest,
+            Some(tmp_dir),
+            None,
+        )
+        .unwrap();
+        let expected = account.derive(Chain::Receive, 0).unwrap();
+        let address = watch_only.next_receive_address().unwrap();
+        assert_eq!(address, expected.address);
+        assert!(address.to_string().starts_with("bcrt1"));
+
+        let reopened = WatchOnlyWallet::open(Some(tmp_dir), None).unwrap();
+        assert_eq!(reopened.network, Network::Regtest);
+        assert_eq!(reopened.address_type, AddressType::P2wpkh);
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+
+    #[test]
+    fn test_watch_only_locking_and_permissions() {
+        let tmp_dir = "/tmp/test_watch_only_locking";
+        let _ = fs::remove_dir_all(tmp_dir);
+
+        let address = FreeWebMovementAddress::random();
+        let account = HdAccount::from_address(&address, "", AccountIndexes::default()).unwrap();
+        let xpub = account.xpub().unwrap().to_string();
+        let path = PathBuf::from(tmp_dir).join(DEFAULT_WATCH_ONLY_FILE);
+
+        // 其他进程持有锁时，导入等待锁释放后才检查并写入文件
+        fs::create_dir_all(tmp_dir).unwrap();
+        let lock = WalletLock::acquire(&path, LockMode::Exclusive, Duration::ZERO).unwrap();
+        let holder = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            drop(lock);
+        });
+        let started = std::time::Instant::now();
+        let mut first = WatchOnlyWallet::from_xpub(
+            &xpub,
+            AddressType::P2pkh,
+            Network::Bitcoin,
+            Some(tmp_dir),
+            None,
+        )
+        .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        holder.join().unwrap();
+        let mut second = WatchOnlyWallet::open(Some(tmp_dir), None).unwrap();
+
+        // 两个句柄交替派生，不会得到相同地址
+        let a = first.next_receive_address().unwrap();
+        let b = second.next_receive_address().unwrap();
+        assert_ne!(a, b);
+        assert_eq!(second.hd.next_receive_index, 2);
+
+        let lock = WalletLock::acquire(&path, LockMode::Exclusive, Duration::ZERO).unwrap();
+        first.set_lock_timeout(Duration::from_millis(100));
+        assert!(matches!(first.save(), Err(AccountError::WalletLocked(_))));
+        assert!(matches!(first.load(), Err(AccountError::WalletLocked(_))));
+        drop(lock);
+
+        // 权限过宽的文件在严格模式下拒绝读取
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(matches!(
+                WatchOnlyWallet::open_with_policy(Some(tmp_dir), None, PermissionPolicy::Strict),
+                Err(AccountError::Permissions(_))
+            ));
+            assert!(WatchOnlyWallet::open(Some(tmp_dir), None).is_ok());
+        }
+
+        let _ = fs::remove_dir_all(tmp_dir);
+    }
+}